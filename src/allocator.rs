@@ -0,0 +1,69 @@
+#[cfg(not(feature = "portable-atomic"))]
+use core::sync::atomic::{AtomicU64, Ordering};
+#[cfg(feature = "portable-atomic")]
+use portable_atomic::{AtomicU64, Ordering};
+
+/// A generator of identifiers that are unique at runtime
+///
+/// It's the runtime counterpart of [`new!`](crate::new!): every identifier it returns
+/// is different from all the others returned by the same allocator, and it only relies
+/// on an atomic counter thus it works without the standard library.
+///
+/// On targets lacking 64-bit atomics the `portable-atomic` feature
+/// can be enabled to use the implementation of the `portable-atomic` crate.
+/// Targets that lack atomic CAS altogether also need either the `critical-section`
+/// or the `unsafe-assume-single-core` feature, which are forwarded to that crate.
+///
+/// # Overflow
+///
+/// Identifiers are never reused: once all of them have been returned the
+/// allocator is exhausted and [`TagAllocator::try_allocate`] returns [`None`],
+/// while [`TagAllocator::allocate`] panics.
+///
+/// # Example
+///
+/// ```
+/// use unique_type::TagAllocator;
+///
+/// static IDS: TagAllocator = TagAllocator::new();
+///
+/// let a = IDS.allocate();
+/// let b = IDS.allocate();
+/// assert_ne!(a, b);
+/// ```
+#[derive(Debug)]
+pub struct TagAllocator {
+    next: AtomicU64,
+}
+
+impl TagAllocator {
+    /// Constructs a new allocator, which can be used to initialize a `static`
+    pub const fn new() -> Self {
+        Self {
+            next: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns a new identifier, or [`None`] if the allocator is exhausted
+    pub fn try_allocate(&self) -> Option<u64> {
+        self.next
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |id| id.checked_add(1))
+            .ok()
+    }
+
+    /// Returns a new identifier
+    ///
+    /// # Panics
+    ///
+    /// Panics if the allocator is exhausted.
+    pub fn allocate(&self) -> u64 {
+        self.try_allocate()
+            .expect("the identifiers of the allocator have been exhausted")
+    }
+}
+
+impl Default for TagAllocator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
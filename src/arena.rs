@@ -0,0 +1,162 @@
+//! An arena whose handles are statically tied to it
+//!
+//! An [`Arena`] is tagged with a [`Unique`] type which is shared by all the
+//! [`Handle`]s it allocates, those can then only be dereferenced with the arena
+//! that allocated them, without requiring any runtime check.
+//!
+//! # Example
+//!
+//! ```
+//! use unique_type::{arena::{Arena, Handle}, Unique};
+//!
+//! enum Expr<Tag: Unique> {
+//!     Num(i32),
+//!     Add(Handle<Tag, Expr<Tag>>, Handle<Tag, Expr<Tag>>),
+//! }
+//!
+//! fn eval<Tag: Unique>(arena: &Arena<Tag, Expr<Tag>>, expr: Handle<Tag, Expr<Tag>>) -> i32 {
+//!     match arena[expr] {
+//!         Expr::Num(n) => n,
+//!         Expr::Add(a, b) => eval(arena, a) + eval(arena, b),
+//!     }
+//! }
+//!
+//! unique_type::with_unique(|brand| {
+//!     let mut arena = Arena::new(brand);
+//!     let a = arena.alloc(Expr::Num(1));
+//!     let b = arena.alloc(Expr::Num(2));
+//!     let sum = arena.alloc(Expr::Add(a, b));
+//!     assert_eq!(eval(&arena, sum), 3);
+//! });
+//! ```
+//!
+//! Using a handle with an arena it doesn't belong to results in a compiler error:
+#![cfg_attr(feature = "nightly", doc = " ```compile_fail E0308")]
+#![cfg_attr(not(feature = "nightly"), doc = " ```ignore")]
+//! # fn main() {
+//! use unique_type::arena::Arena;
+//!
+//! let mut a = Arena::new(unique_type::new_value!());
+//! let b = Arena::<_, usize>::new(unique_type::new_value!());
+//! let handle = a.alloc(0);
+//! // a and b have two different tags
+//! b[handle];
+//! # }
+//! ```
+//!
+//! And the same goes for types generated from [`with_unique`](crate::with_unique):
+//! ```compile_fail E0521
+//! use unique_type::{arena::Arena, with_unique};
+//!
+//! with_unique(|a| with_unique(|b| {
+//!     let mut a = Arena::new(a);
+//!     let b = Arena::<_, usize>::new(b);
+//!     let handle = a.alloc(0);
+//!     b[handle];
+//! }));
+//! ```
+
+use core::{fmt, marker::PhantomData, ops, slice};
+
+use crate::{
+    vec::{BrandedVec, Index},
+    Brand, Unique,
+};
+
+/// An arena of values of type `T` tagged with the unique type `Tag`
+///
+/// The tag guarantees that a [`Handle`] can only be used with the arena that allocated it,
+/// and since values are never deallocated such a handle is always valid.
+pub struct Arena<Tag: Unique, T> {
+    values: BrandedVec<Tag, T>,
+}
+
+impl<Tag: Unique, T> Arena<Tag, T> {
+    /// Constructs a new empty arena tagged with `Tag`
+    ///
+    /// The brand is consumed, this guarantees that no other arena can have the same tag.
+    pub fn new(brand: Brand<Tag>) -> Self {
+        Self {
+            values: BrandedVec::new(brand),
+        }
+    }
+
+    /// Moves `value` into the arena, returning its handle
+    pub fn alloc(&mut self, value: T) -> Handle<Tag, T> {
+        Handle::new(self.values.push(value))
+    }
+
+    /// Returns a reference to the value of the given handle
+    pub fn get(&self, handle: Handle<Tag, T>) -> &T {
+        self.values.get(handle.index)
+    }
+
+    /// Returns a mutable reference to the value of the given handle
+    pub fn get_mut(&mut self, handle: Handle<Tag, T>) -> &mut T {
+        self.values.get_mut(handle.index)
+    }
+
+    /// Returns the number of values in the arena
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if the arena contains no values
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns an iterator over the handles of all the values in the arena
+    pub fn handles(&self) -> impl Iterator<Item = Handle<Tag, T>> {
+        self.values.indices().map(Handle::new)
+    }
+
+    /// Returns an iterator over the values in the arena, in allocation order
+    pub fn iter(&self) -> slice::Iter<'_, T> {
+        self.values.iter()
+    }
+}
+
+impl<Tag: Unique, T> ops::Index<Handle<Tag, T>> for Arena<Tag, T> {
+    type Output = T;
+
+    fn index(&self, handle: Handle<Tag, T>) -> &T {
+        self.get(handle)
+    }
+}
+
+impl<Tag: Unique, T> ops::IndexMut<Handle<Tag, T>> for Arena<Tag, T> {
+    fn index_mut(&mut self, handle: Handle<Tag, T>) -> &mut T {
+        self.get_mut(handle)
+    }
+}
+
+impl<Tag: Unique, T: fmt::Debug> fmt::Debug for Arena<Tag, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.values.fmt(f)
+    }
+}
+
+/// A handle to a value of type `T` in an [`Arena`] tagged with the unique type `Tag`
+///
+/// It can only be generated by the arena with the same tag, thus it's always valid.
+pub struct Handle<Tag: Unique, T> {
+    index: Index<Tag>,
+    _type: PhantomData<fn() -> T>,
+}
+
+impl<Tag: Unique, T> Handle<Tag, T> {
+    const fn new(index: Index<Tag>) -> Self {
+        Self {
+            index,
+            _type: PhantomData,
+        }
+    }
+
+    /// Returns the position of the value in allocation order
+    pub const fn get(self) -> usize {
+        self.index.get()
+    }
+}
+
+id_traits!(Handle<Tag, T>, |handle| handle.index.get());
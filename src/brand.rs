@@ -0,0 +1,97 @@
+use core::{fmt, marker::PhantomData};
+
+use crate::Unique;
+
+/// The proof of ownership of the unique type `Tag`
+///
+/// At most one brand exists for each unique type, thus owning one proves that
+/// nothing else can be tagged with the same type. This is why the brand
+/// implements neither [`Clone`] nor [`Copy`].
+///
+/// Brands are generated by the [`new_value!`](crate::new_value!) macro and
+/// by the [`with_unique`](crate::with_unique) function.
+///
+/// # Usage
+///
+/// A brand can be consumed to tag a value, making it the only one with that tag:
+#[cfg_attr(feature = "alloc", doc = " ```")]
+#[cfg_attr(not(feature = "alloc"), doc = " ```ignore")]
+/// unique_type::with_unique(|brand| {
+///     let vec = unique_type::vec::BrandedVec::<_, usize>::new(brand);
+/// });
+/// ```
+///
+/// Or it can be borrowed to prove the ownership of the tag without giving it up:
+/// ```
+/// fn privileged<Tag: unique_type::Unique>(_: &unique_type::Brand<Tag>) {
+///     // ...
+/// }
+///
+/// unique_type::with_unique(|brand| {
+///     privileged(&brand);
+///     privileged(&brand);
+/// });
+/// ```
+pub struct Brand<Tag: Unique>(PhantomData<Tag>);
+
+impl<Tag: Unique> Brand<Tag> {
+    /// Constructs the brand of `Tag`
+    ///
+    /// # Safety
+    ///
+    /// This function is safe only if no other brand is ever constructed for `Tag`.
+    ///
+    /// For example, this is a valid usage:
+    #[cfg_attr(feature = "nightly", doc = " ```")]
+    #[cfg_attr(not(feature = "nightly"), doc = " ```ignore")]
+    /// # fn main() {
+    /// # use unique_type::Brand;
+    /// let brand = unsafe { Brand::<unique_type::new!()>::new_unchecked() };
+    /// # }
+    /// ```
+    /// because every call to [`new!`](crate::new!) generates a different type.
+    ///
+    /// While this is an unsafe usage:
+    #[cfg_attr(feature = "nightly", doc = " ```")]
+    #[cfg_attr(not(feature = "nightly"), doc = " ```ignore")]
+    /// # fn main() {
+    /// # use unique_type::Brand;
+    /// for _ in 0..2 {
+    ///     let brand = unsafe { Brand::<unique_type::new!()>::new_unchecked() };
+    /// }
+    /// # }
+    /// ```
+    /// because the same type is used for two different brands.
+    pub const unsafe fn new_unchecked() -> Self {
+        Self(PhantomData)
+    }
+
+    /// Returns a reference to the brand of `Tag` without requiring to own it
+    ///
+    /// # Safety
+    ///
+    /// This function is safe only if the brand of `Tag` has already been constructed
+    /// and the caller owns it or something that took its place (like a [`DynTag`](crate::DynTag)).
+    pub(crate) const unsafe fn ref_unchecked<'a>() -> &'a Self {
+        &Self(PhantomData)
+    }
+}
+
+/// Shows the label of the unique type and where it was generated, if known
+impl<Tag: Unique> fmt::Debug for Brand<Tag> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut tuple = f.debug_tuple("Brand");
+        debug_tag::<Tag>(&mut tuple);
+        tuple.finish()
+    }
+}
+
+/// Adds the label of `Tag` and the location where it was generated, if known
+pub(crate) fn debug_tag<Tag: Unique>(tuple: &mut fmt::DebugTuple<'_, '_>) {
+    if let Some(label) = <Tag as Unique>::LABEL {
+        tuple.field(&label);
+    }
+    if let Some(origin) = Tag::origin() {
+        tuple.field(&format_args!("{origin}"));
+    }
+}
@@ -0,0 +1,192 @@
+//! Access control through capabilities derived from the brand of a unique type
+//!
+//! The [`Brand`] of a unique type can be [`split`] into a read capability and a write
+//! capability, [`Cap<Tag, Read>`] and [`Cap<Tag, Write>`], which can later be [`join`]ed
+//! back into the brand. The contents of a [`Guarded`] value can then only be reached
+//! through the matching capability, even by whoever owns the value.
+//!
+//! # Example
+//!
+//! ```
+//! use unique_type::{capability::{self, Cap, Guarded, Read, Write}, Unique};
+//!
+//! struct Account {
+//!     balance: u64,
+//! }
+//!
+//! fn balance<Tag: Unique>(account: &Guarded<Tag, Account>, cap: &Cap<Tag, Read>) -> u64 {
+//!     account.get(cap).balance
+//! }
+//!
+//! fn deposit<Tag: Unique>(account: &mut Guarded<Tag, Account>, cap: &Cap<Tag, Write>, amount: u64) {
+//!     account.get_mut(cap).balance += amount;
+//! }
+//!
+//! unique_type::with_unique(|brand| {
+//!     let (read, write) = capability::split(brand);
+//!     let mut account = Guarded::new(&write, Account { balance: 0 });
+//!     deposit(&mut account, &write, 10);
+//!     assert_eq!(balance(&account, &read), 10);
+//!     assert_eq!(balance(&account, write.as_read()), 10);
+//!     let brand = capability::join(read, write);
+//! });
+//! ```
+//!
+//! Using a read capability where a write one is required results in a compiler error:
+//! ```compile_fail E0308
+//! use unique_type::capability::{self, Guarded};
+//!
+//! unique_type::with_unique(|brand| {
+//!     let (read, write) = capability::split(brand);
+//!     let mut value = Guarded::new(&write, 0);
+//!     *value.get_mut(&read) += 1;
+//! });
+//! ```
+//!
+//! And the same goes for capabilities of another tag:
+#![cfg_attr(feature = "nightly", doc = " ```compile_fail E0308")]
+#![cfg_attr(not(feature = "nightly"), doc = " ```ignore")]
+//! # fn main() {
+//! use unique_type::capability::{self, Guarded};
+//!
+//! let (_, a) = capability::split(unique_type::new_value!());
+//! let (_, b) = capability::split(unique_type::new_value!());
+//! let mut value = Guarded::new(&a, 0);
+//! // value and b have two different tags
+//! *value.get_mut(&b) += 1;
+//! # }
+//! ```
+//!
+//! Including types generated from [`with_unique`](crate::with_unique):
+//! ```compile_fail E0521
+//! use unique_type::{capability::{self, Guarded}, with_unique};
+//!
+//! with_unique(|a| with_unique(|b| {
+//!     let (_, a) = capability::split(a);
+//!     let (_, b) = capability::split(b);
+//!     let mut value = Guarded::new(&a, 0);
+//!     *value.get_mut(&b) += 1;
+//! }));
+//! ```
+//!
+//! Unlike a [`Tagged`] value, the contents of a guarded value can't be reached without a capability:
+//! ```compile_fail E0614
+//! use unique_type::capability::{self, Guarded};
+//!
+//! unique_type::with_unique(|brand| {
+//!     let (_, write) = capability::split(brand);
+//!     let value = Guarded::new(&write, 0);
+//!     let _ = *value;
+//! });
+//! ```
+
+use core::{fmt, marker::PhantomData};
+
+use crate::{brand, Brand, Tagged, Unique};
+
+mod pvt {
+    /// Private version of [`Permission`](super::Permission)
+    pub trait Permission {}
+}
+
+/// The kind of access granted by a [`Cap`]
+///
+/// This trait is sealed and implemented only by [`Read`] and [`Write`].
+pub trait Permission: pvt::Permission {}
+
+impl<T: pvt::Permission> Permission for T {}
+
+/// The permission of reading the values tagged with a unique type
+pub struct Read;
+
+impl pvt::Permission for Read {}
+
+/// The permission of modifying the values tagged with a unique type
+pub struct Write;
+
+impl pvt::Permission for Write {}
+
+/// The capability of accessing the values tagged with the unique type `Tag`
+/// as specified by the permission `P`
+///
+/// Just like a [`Brand`] there's at most one capability of each kind for each unique type,
+/// thus it implements neither [`Clone`] nor [`Copy`] and it's meant to be borrowed.
+pub struct Cap<Tag: Unique, P: Permission>(PhantomData<(Tag, P)>);
+
+/// Splits the brand of `Tag` into its read and write capabilities
+pub fn split<Tag: Unique>(_brand: Brand<Tag>) -> (Cap<Tag, Read>, Cap<Tag, Write>) {
+    (Cap(PhantomData), Cap(PhantomData))
+}
+
+/// Joins the read and write capabilities of `Tag` back into its brand
+pub fn join<Tag: Unique>(_read: Cap<Tag, Read>, _write: Cap<Tag, Write>) -> Brand<Tag> {
+    // SAFETY: capabilities are only generated by consuming the brand of `Tag`,
+    // and both of them are consumed here, thus no other brand of `Tag` exists
+    unsafe { Brand::new_unchecked() }
+}
+
+impl<Tag: Unique> Cap<Tag, Write> {
+    /// Returns the read capability implied by this one
+    pub const fn as_read(&self) -> &Cap<Tag, Read> {
+        &Cap(PhantomData)
+    }
+
+    /// Tags `value` with `Tag`, just like [`Tagged::new`] does with the brand
+    pub const fn tag<T>(&self, value: T) -> Tagged<Tag, T> {
+        Tagged::from_inner(value)
+    }
+}
+
+/// A value of type `T` that can only be accessed through the capabilities of `Tag`
+///
+/// Creating a guarded value requires the write capability, reading it requires
+/// the read one and modifying it requires the write one. It doesn't give access to
+/// its contents in any other way, thus owning it or borrowing it isn't enough.
+pub struct Guarded<Tag: Unique, T> {
+    value: T,
+    _tag: PhantomData<Tag>,
+}
+
+impl<Tag: Unique, T> Guarded<Tag, T> {
+    /// Guards `value` with the capabilities of `Tag`
+    pub const fn new(_cap: &Cap<Tag, Write>, value: T) -> Self {
+        Self {
+            value,
+            _tag: PhantomData,
+        }
+    }
+
+    /// Returns a reference to the guarded value
+    pub const fn get(&self, _cap: &Cap<Tag, Read>) -> &T {
+        &self.value
+    }
+
+    /// Returns a mutable reference to the guarded value
+    pub fn get_mut(&mut self, _cap: &Cap<Tag, Write>) -> &mut T {
+        &mut self.value
+    }
+
+    /// Consumes the guarded value, returning it
+    pub fn into_inner(self, _cap: &Cap<Tag, Write>) -> T {
+        self.value
+    }
+}
+
+/// Shows the label of the unique type and where it was generated, if known,
+/// the guarded value is not shown as it requires the read capability
+impl<Tag: Unique, T> fmt::Debug for Guarded<Tag, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut tuple = f.debug_tuple("Guarded");
+        brand::debug_tag::<Tag>(&mut tuple);
+        tuple.finish()
+    }
+}
+
+/// Shows the label of the unique type and where it was generated, if known
+impl<Tag: Unique, P: Permission> fmt::Debug for Cap<Tag, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut tuple = f.debug_tuple("Cap");
+        brand::debug_tag::<Tag>(&mut tuple);
+        tuple.finish()
+    }
+}
@@ -0,0 +1,180 @@
+//! Zero-cost interior mutability controlled by a unique token
+//!
+//! A [`TagToken`] and all the [`TagCell`]s with the same [`Unique`] tag work together
+//! like a [`RefCell`](core::cell::RefCell) would, but with the borrow rules checked at
+//! compile time: borrowing the token immutably allows to read any number of cells,
+//! while borrowing it mutably allows to write to one of them (this is also known as the GhostCell pattern).
+//!
+//! # Example
+//!
+//! ```
+//! use unique_type::cell::{TagCell, TagToken};
+//!
+//! unique_type::with_unique(|brand| {
+//!     let mut token = TagToken::new(brand);
+//!     let a = TagCell::new(1);
+//!     let b = TagCell::new(2);
+//!     let cells = [&a, &b, &a];
+//!
+//!     *cells[2].borrow_mut(&mut token) += 10;
+//!     let (a, b) = token.borrow_mut2(cells[0], cells[1]);
+//!     core::mem::swap(a, b);
+//!
+//!     assert_eq!(*cells[0].borrow(&token), 2);
+//!     assert_eq!(*cells[1].borrow(&token), 11);
+//! });
+//! ```
+//!
+//! A cell can't be accessed with a token that has a different tag:
+#![cfg_attr(feature = "nightly", doc = " ```compile_fail E0308")]
+#![cfg_attr(not(feature = "nightly"), doc = " ```ignore")]
+//! # fn main() {
+//! use unique_type::cell::{TagCell, TagToken};
+//!
+//! let token = TagToken::new(unique_type::new_value!());
+//! let cell = TagCell::<unique_type::new!(), _>::new(0);
+//! // token and cell have two different tags
+//! cell.borrow(&token);
+//! # }
+//! ```
+//!
+//! And a cell can't be read while another one is being written:
+//! ```compile_fail E0502
+//! use unique_type::cell::{TagCell, TagToken};
+//!
+//! unique_type::with_unique(|brand| {
+//!     let mut token = TagToken::new(brand);
+//!     let a = TagCell::new(1);
+//!     let b = TagCell::new(2);
+//!     let a = a.borrow_mut(&mut token);
+//!     *a = *b.borrow(&token);
+//! });
+//! ```
+
+use core::{cell::UnsafeCell, marker::PhantomData};
+
+use crate::{Brand, Unique};
+
+/// The token that controls the access to all the [`TagCell`]s tagged with `Tag`
+///
+/// Only one token can exist for each tag, thus the borrow of the token
+/// is also a borrow of all the cells with the same tag.
+pub struct TagToken<Tag: Unique>(PhantomData<Tag>);
+
+impl<Tag: Unique> TagToken<Tag> {
+    /// Constructs the token for `Tag`
+    ///
+    /// The brand is consumed, this guarantees that no other token can have the same tag.
+    pub fn new(_brand: Brand<Tag>) -> Self {
+        Self(PhantomData)
+    }
+
+    /// Mutably borrows the content of two different cells at the same time
+    ///
+    /// # Panics
+    ///
+    /// Panics if the two cells overlap in memory.
+    pub fn borrow_mut2<'a, A: ?Sized, B: ?Sized>(
+        &'a mut self,
+        a: &'a TagCell<Tag, A>,
+        b: &'a TagCell<Tag, B>,
+    ) -> (&'a mut A, &'a mut B) {
+        assert!(!overlap(a, b), "the cells overlap");
+        // SAFETY: the token is borrowed mutably, so no other reference to the content
+        // of any cell with this tag can exist, and the two cells don't overlap
+        unsafe { (&mut *a.value.get(), &mut *b.value.get()) }
+    }
+
+    /// Mutably borrows the content of three different cells at the same time
+    ///
+    /// # Panics
+    ///
+    /// Panics if any two of the cells overlap in memory.
+    pub fn borrow_mut3<'a, A: ?Sized, B: ?Sized, C: ?Sized>(
+        &'a mut self,
+        a: &'a TagCell<Tag, A>,
+        b: &'a TagCell<Tag, B>,
+        c: &'a TagCell<Tag, C>,
+    ) -> (&'a mut A, &'a mut B, &'a mut C) {
+        assert!(
+            !overlap(a, b) && !overlap(a, c) && !overlap(b, c),
+            "the cells overlap"
+        );
+        // SAFETY: the token is borrowed mutably, so no other reference to the content
+        // of any cell with this tag can exist, and the three cells don't overlap
+        unsafe {
+            (
+                &mut *a.value.get(),
+                &mut *b.value.get(),
+                &mut *c.value.get(),
+            )
+        }
+    }
+}
+
+/// A cell whose content can only be accessed through the [`TagToken`] of the same tag
+#[repr(transparent)]
+pub struct TagCell<Tag: Unique, T: ?Sized> {
+    _tag: PhantomData<Tag>,
+    value: UnsafeCell<T>,
+}
+
+// SAFETY: sharing the cell allows to access the content as `&T` (requiring `T: Sync`)
+// and, to whoever owns the token, as `&mut T` (requiring `T: Send`)
+unsafe impl<Tag: Unique, T: ?Sized + Send + Sync> Sync for TagCell<Tag, T> {}
+
+impl<Tag: Unique, T> TagCell<Tag, T> {
+    /// Constructs a new cell containing `value`
+    pub const fn new(value: T) -> Self {
+        Self {
+            _tag: PhantomData,
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    /// Consumes the cell, returning its content
+    pub fn into_inner(self) -> T {
+        self.value.into_inner()
+    }
+}
+
+impl<Tag: Unique, T: ?Sized> TagCell<Tag, T> {
+    /// Immutably borrows the content of the cell
+    pub fn borrow<'a>(&'a self, _token: &'a TagToken<Tag>) -> &'a T {
+        // SAFETY: the token is borrowed immutably, so no mutable reference to the content
+        // of any cell with this tag can exist: the token is the only one for this tag
+        // as it's constructed from its brand, and every specialization of `Template` (and of `Scoped`) is a different type
+        unsafe { &*self.value.get() }
+    }
+
+    /// Mutably borrows the content of the cell
+    pub fn borrow_mut<'a>(&'a self, _token: &'a mut TagToken<Tag>) -> &'a mut T {
+        // SAFETY: the token is borrowed mutably, so no other reference to the content
+        // of any cell with this tag can exist: the token is the only one for this tag
+        // as it's constructed from its brand, and every specialization of `Template` (and of `Scoped`) is a different type
+        unsafe { &mut *self.value.get() }
+    }
+
+    /// Returns a mutable reference to the content of the cell
+    ///
+    /// This doesn't require the token as the cell itself is borrowed mutably.
+    pub fn get_mut(&mut self) -> &mut T {
+        self.value.get_mut()
+    }
+
+    /// Returns a cell from a mutable reference
+    pub fn from_mut(value: &mut T) -> &Self {
+        // SAFETY: the cell is `repr(transparent)` over `UnsafeCell<T>`,
+        // which in turn has the same memory layout of `T`,
+        // and the mutable borrow guarantees that `value` is not aliased
+        unsafe { &*(value as *mut T as *const Self) }
+    }
+}
+
+/// Returns `true` if the two values share any byte of memory
+fn overlap<A: ?Sized, B: ?Sized>(a: &A, b: &B) -> bool {
+    let (a_size, b_size) = (core::mem::size_of_val(a), core::mem::size_of_val(b));
+    let a = (a as *const A).cast::<u8>().addr();
+    let b = (b as *const B).cast::<u8>().addr();
+    a_size != 0 && b_size != 0 && a < b + b_size && b < a + a_size
+}
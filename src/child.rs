@@ -0,0 +1,88 @@
+use core::marker::PhantomData;
+
+use crate::{pvt, Brand, Origin, Unique};
+
+/// A unique type derived from the unique type `Parent`
+///
+/// It's unique in its own right as `Tag` is unique, but it also implements
+/// [`SubTagOf<Parent>`] thus it keeps track of the type it derives from.
+///
+/// Its origin and label are the ones of `Tag`.
+///
+/// # Example
+///
+/// ```
+/// use unique_type::{with_unique, Brand, SubTagOf, Unique};
+///
+/// fn handle<Session: Unique, Request: SubTagOf<Session>>(
+///     _session: &Brand<Session>,
+///     _request: &Brand<Request>,
+/// ) {
+///     // ...
+/// }
+///
+/// with_unique(|session| {
+///     for _ in 0..2 {
+///         with_unique(|request| {
+///             let request = session.child(request);
+///             handle(&session, &request);
+///         });
+///     }
+/// });
+/// ```
+///
+/// Types that don't derive from the required parent are rejected:
+/// ```compile_fail E0277
+/// # use unique_type::{with_unique, Brand, SubTagOf, Unique};
+/// # fn handle<Session: Unique, Request: SubTagOf<Session>>(_: &Brand<Session>, _: &Brand<Request>) {}
+/// with_unique(|session| with_unique(|request| {
+///     handle(&session, &request);
+/// }));
+/// ```
+///
+/// On the nightly toolchain the child types can also be named:
+#[cfg_attr(feature = "nightly", doc = " ```")]
+#[cfg_attr(not(feature = "nightly"), doc = " ```ignore")]
+/// # fn main() {
+/// use unique_type::{Child, SubTagOf};
+///
+/// type Session = unique_type::new!();
+/// type Request = Child<Session, unique_type::new!()>;
+///
+/// fn require<Tag: SubTagOf<Session>>() {}
+/// require::<Request>();
+/// # }
+/// ```
+pub struct Child<Parent: Unique, Tag: Unique>(PhantomData<(Parent, Tag)>);
+
+impl<Parent: Unique, Tag: Unique> pvt::Unique for Child<Parent, Tag> {
+    const ORIGIN: Option<Origin> = Tag::ORIGIN;
+    const TAG_LABEL: Option<&'static str> = Tag::TAG_LABEL;
+}
+
+mod sealed {
+    /// Private version of [`SubTagOf`](super::SubTagOf)
+    pub trait SubTagOf<Parent> {}
+}
+
+/// An interface for requiring unique types derived from `Parent`
+///
+/// The only types implementing this trait are the [`Child`]ren of `Parent`,
+/// grandchildren only implement it for their direct parent.
+pub trait SubTagOf<Parent: Unique>: Unique + sealed::SubTagOf<Parent> {}
+
+impl<Parent: Unique, Tag: Unique> sealed::SubTagOf<Parent> for Child<Parent, Tag> {}
+
+impl<Parent: Unique, Tag: Unique> SubTagOf<Parent> for Child<Parent, Tag> {}
+
+impl<Parent: Unique> Brand<Parent> {
+    /// Derives a child of `Parent` from `Tag`, consuming the brand of the latter
+    ///
+    /// Borrowing the brand of `Parent` proves that the child is derived
+    /// from the owner of the parent type.
+    pub const fn child<Tag: Unique>(&self, _brand: Brand<Tag>) -> Brand<Child<Parent, Tag>> {
+        // SAFETY: the brand of `Tag` is consumed, and a child type
+        // can only be constructed from it, thus it's the only one
+        unsafe { Brand::new_unchecked() }
+    }
+}
@@ -0,0 +1,119 @@
+use core::{fmt, marker::PhantomData};
+
+use crate::{same_tag, Brand, Unique};
+
+/// The proof that `A` and `B` are two different unique types
+///
+/// A type mismatch can only reject two tags that must be the same, this witness
+/// instead lets an API require two tags that must be different, even if they are generic.
+///
+/// It can be obtained at compile time with [`Distinct::assert`] (requires the `nightly`
+/// feature), at runtime with [`Distinct::check`], or from the brands of the two types
+/// with [`Distinct::from_brands`] which also works for the types generated from
+/// [`with_unique`](crate::with_unique).
+///
+/// # Example
+///
+#[cfg_attr(feature = "alloc", doc = " ```")]
+#[cfg_attr(not(feature = "alloc"), doc = " ```ignore")]
+/// use unique_type::{vec::BrandedVec, with_unique, Distinct, Unique};
+///
+/// fn merge<A: Unique, B: Unique>(
+///     into: &mut BrandedVec<A, u32>,
+///     from: &BrandedVec<B, u32>,
+///     _: Distinct<A, B>,
+/// ) {
+///     for &value in from.iter() {
+///         into.push(value);
+///     }
+/// }
+///
+/// with_unique(|mut a| with_unique(|mut b| {
+///     let distinct = Distinct::from_brands(&mut a, &mut b);
+///     let mut a = BrandedVec::new(a);
+///     let mut b = BrandedVec::new(b);
+///     a.push(1);
+///     b.push(2);
+///     merge(&mut a, &b, distinct);
+///     assert_eq!(a.as_slice(), [1, 2]);
+/// }));
+/// ```
+///
+/// The same brand can't prove that its type is different from itself:
+/// ```compile_fail E0499
+/// unique_type::with_unique(|mut a| {
+///     unique_type::Distinct::from_brands(&mut a, &mut a);
+/// });
+/// ```
+pub struct Distinct<A: Unique, B: Unique>(PhantomData<(A, B)>);
+
+impl<A: Unique, B: Unique> Distinct<A, B> {
+    /// Proves that `A` and `B` are different from their brands
+    ///
+    /// There's at most one brand for each unique type, and the same brand
+    /// can't be mutably borrowed twice, thus the two types must be different.
+    pub fn from_brands(_a: &mut Brand<A>, _b: &mut Brand<B>) -> Self {
+        Self(PhantomData)
+    }
+
+    /// Returns the proof that `B` and `A` are different
+    pub const fn flip(self) -> Distinct<B, A> {
+        Distinct(PhantomData)
+    }
+}
+
+impl<A: Unique + 'static, B: Unique + 'static> Distinct<A, B> {
+    /// Proves that `A` and `B` are different by comparing them at runtime,
+    /// returning [`None`] if they are the same type
+    pub fn check() -> Option<Self> {
+        (!same_tag::<A, B>()).then_some(Self(PhantomData))
+    }
+
+    /// Proves that `A` and `B` are different by comparing them at compile time
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "nightly", doc = " ```")]
+    #[cfg_attr(not(feature = "nightly"), doc = " ```ignore")]
+    /// # fn main() {
+    /// use unique_type::Distinct;
+    ///
+    /// type A = unique_type::new!();
+    /// type B = unique_type::new!();
+    /// const DISTINCT: Distinct<A, B> = Distinct::assert();
+    /// # }
+    /// ```
+    ///
+    /// If they are the same type the compilation fails:
+    #[cfg_attr(feature = "nightly", doc = " ```compile_fail E0080")]
+    #[cfg_attr(not(feature = "nightly"), doc = " ```ignore")]
+    /// # fn main() {
+    /// use unique_type::Distinct;
+    ///
+    /// type A = unique_type::new!();
+    /// const DISTINCT: Distinct<A, A> = Distinct::assert();
+    /// # }
+    /// ```
+    #[cfg(feature = "nightly")]
+    pub const fn assert() -> Self {
+        assert!(!same_tag::<A, B>(), "the two unique types are the same");
+        Self(PhantomData)
+    }
+}
+
+// The following traits are implemented manually
+// as deriving them would require `A` and `B` to implement them too
+
+impl<A: Unique, B: Unique> Clone for Distinct<A, B> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<A: Unique, B: Unique> Copy for Distinct<A, B> {}
+
+impl<A: Unique, B: Unique> fmt::Debug for Distinct<A, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Distinct")
+    }
+}
@@ -0,0 +1,177 @@
+use core::fmt;
+
+use crate::{Brand, TagId, Unique};
+
+/// A brand chosen at runtime
+///
+/// Just like a [`Brand`] it's different from every other one and it implements neither
+/// [`Clone`] nor [`Copy`], but its uniqueness is checked at runtime through its [`DynId`]
+/// instead of being enforced by the type system. This makes it possible to brand values
+/// that are created dynamically, e.g. one for each plugin loaded at runtime,
+/// through [`DynTag::tag`].
+///
+/// A dynamic brand can also be constructed from the brand of a `'static` unique type,
+/// in which case [`DynTag::try_assume`] gives back a statically branded view.
+///
+/// # Example
+///
+/// ```
+/// use unique_type::DynTag;
+///
+/// let plugins: Vec<DynTag> = (0..3).map(|_| DynTag::new()).collect();
+/// assert_ne!(plugins[0].id(), plugins[1].id());
+/// assert_ne!(plugins[1].id(), plugins[2].id());
+///
+/// let config = plugins[0].tag("config");
+/// assert_eq!(config.get(&plugins[0]), Some(&"config"));
+/// assert_eq!(config.get(&plugins[1]), None);
+/// ```
+///
+/// Converting back to a static brand:
+#[cfg_attr(all(feature = "nightly", feature = "alloc"), doc = " ```")]
+#[cfg_attr(not(all(feature = "nightly", feature = "alloc")), doc = " ```ignore")]
+/// # fn main() {
+/// use unique_type::{vec::BrandedVec, Brand, DynTag, Unique};
+///
+/// fn load<Tag: Unique + 'static>(brand: Brand<Tag>) -> BrandedVec<Tag, usize> {
+///     let tag = DynTag::from_brand(brand);
+///     assert!(tag.try_assume::<Tag>().is_some());
+///     BrandedVec::new(tag.try_into_brand().unwrap())
+/// }
+///
+/// let tag = DynTag::from_brand(unique_type::new_value!());
+/// assert!(tag.try_assume::<unique_type::new!()>().is_none());
+/// let vec = load(unique_type::new_value!());
+/// # }
+/// ```
+pub struct DynTag {
+    id: DynId,
+}
+
+/// The identifier of a [`DynTag`]
+///
+/// Two identifiers are equal only if they belong to the same dynamic brand.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct DynId(Repr);
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+enum Repr {
+    /// Generated by the global [`TagAllocator`](crate::TagAllocator)
+    Runtime(u64),
+    /// The identifier of the unique type whose brand was converted
+    Static(TagId),
+}
+
+/// The allocator of the identifiers of [`DynTag::new`]
+#[cfg(any(feature = "portable-atomic", target_has_atomic = "64"))]
+static IDS: crate::TagAllocator = crate::TagAllocator::new();
+
+impl DynTag {
+    /// Constructs a new dynamic brand, different from every other one
+    ///
+    /// # Panics
+    ///
+    /// Panics if all the identifiers have been used.
+    #[cfg(any(feature = "portable-atomic", target_has_atomic = "64"))]
+    pub fn new() -> Self {
+        Self {
+            id: DynId(Repr::Runtime(IDS.allocate())),
+        }
+    }
+
+    /// Converts the brand of a `'static` unique type into a dynamic one
+    pub fn from_brand<Tag: Unique + 'static>(_brand: Brand<Tag>) -> Self {
+        Self {
+            id: DynId(Repr::Static(TagId::of::<Tag>())),
+        }
+    }
+
+    /// Returns the identifier of the dynamic brand
+    pub fn id(&self) -> DynId {
+        self.id
+    }
+
+    /// Tags `value` with this dynamic brand, it can then only be accessed through it
+    pub fn tag<T>(&self, value: T) -> DynTagged<T> {
+        DynTagged { id: self.id, value }
+    }
+
+    /// Returns `true` if the dynamic brand was converted from the brand of `Tag`
+    pub fn is<Tag: Unique + 'static>(&self) -> bool {
+        self.id == DynId(Repr::Static(TagId::of::<Tag>()))
+    }
+
+    /// Returns a view of the brand of `Tag` if this was converted from it,
+    /// or [`None`] otherwise
+    pub fn try_assume<Tag: Unique + 'static>(&self) -> Option<&Brand<Tag>> {
+        // SAFETY: this was constructed by consuming the brand of `Tag`
+        self.is::<Tag>().then(|| unsafe { Brand::ref_unchecked() })
+    }
+
+    /// Converts the dynamic brand back into the brand of `Tag` if this was converted from it,
+    /// or gives it back otherwise
+    pub fn try_into_brand<Tag: Unique + 'static>(self) -> Result<Brand<Tag>, Self> {
+        match self.is::<Tag>() {
+            // SAFETY: this was constructed by consuming the brand of `Tag`,
+            // and it's consumed here, thus no other brand of `Tag` exists
+            true => Ok(unsafe { Brand::new_unchecked() }),
+            false => Err(self),
+        }
+    }
+}
+
+#[cfg(any(feature = "portable-atomic", target_has_atomic = "64"))]
+impl Default for DynTag {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for DynTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("DynTag").field(&self.id).finish()
+    }
+}
+
+/// A value of type `T` tagged with a [`DynTag`]
+///
+/// It's the runtime counterpart of [`Tagged`](crate::Tagged): the wrapped value can only be
+/// accessed by showing the dynamic brand it was tagged with, which is checked through its id.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct DynTagged<T> {
+    id: DynId,
+    value: T,
+}
+
+impl<T> DynTagged<T> {
+    /// Returns the identifier of the dynamic brand the value is tagged with
+    pub fn id(&self) -> DynId {
+        self.id
+    }
+
+    /// Returns `true` if the value is tagged with `tag`
+    pub fn is_tagged_with(&self, tag: &DynTag) -> bool {
+        self.id == tag.id
+    }
+
+    /// Returns a reference to the wrapped value if it's tagged with `tag`,
+    /// or [`None`] otherwise
+    pub fn get(&self, tag: &DynTag) -> Option<&T> {
+        self.is_tagged_with(tag).then_some(&self.value)
+    }
+
+    /// Returns a mutable reference to the wrapped value if it's tagged with `tag`,
+    /// or [`None`] otherwise
+    pub fn get_mut(&mut self, tag: &DynTag) -> Option<&mut T> {
+        self.is_tagged_with(tag).then_some(&mut self.value)
+    }
+
+    /// Consumes the tagged value returning the wrapped one if it's tagged with `tag`,
+    /// or gives it back otherwise
+    pub fn try_into_inner(self, tag: &DynTag) -> Result<T, Self> {
+        match self.is_tagged_with(tag) {
+            true => Ok(self.value),
+            false => Err(self),
+        }
+    }
+}
@@ -0,0 +1,296 @@
+//! A directed graph whose nodes and edges are statically tied to it
+//!
+//! A [`Graph`] is tagged with a [`Unique`] type which is shared by all the
+//! [`NodeId`]s and [`EdgeId`]s it generates, those can then only be used with the graph
+//! that generated them, thus looking up nodes and edges and traversing the adjacency lists
+//! doesn't require any bound check.
+//!
+//! # Example
+//!
+//! ```
+//! use unique_type::{graph::{Graph, NodeId}, Unique};
+//!
+//! fn reachable<Tag: Unique, N, E>(graph: &Graph<Tag, N, E>, from: NodeId<Tag>) -> Vec<NodeId<Tag>> {
+//!     let mut visited = vec![from];
+//!     let mut stack = vec![from];
+//!     while let Some(node) = stack.pop() {
+//!         for next in graph.neighbors(node) {
+//!             if !visited.contains(&next) {
+//!                 visited.push(next);
+//!                 stack.push(next);
+//!             }
+//!         }
+//!     }
+//!     visited
+//! }
+//!
+//! unique_type::with_unique(|brand| {
+//!     let mut graph = Graph::new(brand);
+//!     let a = graph.add_node("a");
+//!     let b = graph.add_node("b");
+//!     let c = graph.add_node("c");
+//!     let ab = graph.add_edge(a, b, 1);
+//!     graph.add_edge(c, a, 2);
+//!     assert_eq!(graph.endpoints(ab), (a, b));
+//!     assert_eq!(reachable(&graph, a), [a, b]);
+//!     assert_eq!(graph[c], "c");
+//! });
+//! ```
+//!
+//! Using a node with a graph it doesn't belong to results in a compiler error:
+#![cfg_attr(feature = "nightly", doc = " ```compile_fail E0308")]
+#![cfg_attr(not(feature = "nightly"), doc = " ```ignore")]
+//! # fn main() {
+//! use unique_type::graph::Graph;
+//!
+//! let mut a = Graph::<_, usize, ()>::new(unique_type::new_value!());
+//! let b = Graph::<_, usize, ()>::new(unique_type::new_value!());
+//! let node = a.add_node(0);
+//! // a and b have two different tags
+//! b.node(node);
+//! # }
+//! ```
+//!
+//! And the same goes for types generated from [`with_unique`](crate::with_unique):
+//! ```compile_fail E0521
+//! use unique_type::{graph::Graph, with_unique};
+//!
+//! with_unique(|a| with_unique(|b| {
+//!     let mut a = Graph::<_, usize, ()>::new(a);
+//!     let mut b = Graph::<_, usize, ()>::new(b);
+//!     let x = a.add_node(0);
+//!     let y = b.add_node(1);
+//!     b.add_edge(x, y, ());
+//! }));
+//! ```
+
+use alloc::vec::Vec;
+use core::{fmt, marker::PhantomData, ops};
+
+use crate::{Brand, Unique};
+
+/// An append-only directed graph tagged with the unique type `Tag`,
+/// with nodes of type `N` and edges of type `E`
+///
+/// The tag guarantees that [`NodeId`]s and [`EdgeId`]s can only be used with the graph
+/// that created them, and since nodes and edges can never be removed such ids are always valid.
+pub struct Graph<Tag: Unique, N, E> {
+    nodes: Vec<Node<Tag, N>>,
+    edges: Vec<Edge<Tag, E>>,
+    _tag: PhantomData<Tag>,
+}
+
+struct Node<Tag: Unique, N> {
+    value: N,
+    outgoing: Vec<EdgeId<Tag>>,
+}
+
+struct Edge<Tag: Unique, E> {
+    value: E,
+    source: NodeId<Tag>,
+    target: NodeId<Tag>,
+}
+
+impl<Tag: Unique, N, E> Graph<Tag, N, E> {
+    /// Constructs a new empty graph tagged with `Tag`
+    ///
+    /// The brand is consumed, this guarantees that no other graph can have the same tag.
+    pub fn new(_brand: Brand<Tag>) -> Self {
+        Self {
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            _tag: PhantomData,
+        }
+    }
+
+    /// Adds a node to the graph, returning its id
+    pub fn add_node(&mut self, value: N) -> NodeId<Tag> {
+        let id = NodeId::new(self.nodes.len());
+        self.nodes.push(Node {
+            value,
+            outgoing: Vec::new(),
+        });
+        id
+    }
+
+    /// Adds an edge going from `source` to `target` to the graph, returning its id
+    pub fn add_edge(&mut self, source: NodeId<Tag>, target: NodeId<Tag>, value: E) -> EdgeId<Tag> {
+        let id = EdgeId::new(self.edges.len());
+        self.edges.push(Edge {
+            value,
+            source,
+            target,
+        });
+        self.node_entry_mut(source).outgoing.push(id);
+        id
+    }
+
+    fn node_entry(&self, id: NodeId<Tag>) -> &Node<Tag, N> {
+        // SAFETY: the id has been generated by this graph (it has the same tag)
+        // and the nodes are never removed
+        unsafe { self.nodes.get_unchecked(id.index) }
+    }
+
+    fn node_entry_mut(&mut self, id: NodeId<Tag>) -> &mut Node<Tag, N> {
+        // SAFETY: the id has been generated by this graph (it has the same tag)
+        // and the nodes are never removed
+        unsafe { self.nodes.get_unchecked_mut(id.index) }
+    }
+
+    fn edge_entry(&self, id: EdgeId<Tag>) -> &Edge<Tag, E> {
+        // SAFETY: the id has been generated by this graph (it has the same tag)
+        // and the edges are never removed
+        unsafe { self.edges.get_unchecked(id.index) }
+    }
+
+    fn edge_entry_mut(&mut self, id: EdgeId<Tag>) -> &mut Edge<Tag, E> {
+        // SAFETY: the id has been generated by this graph (it has the same tag)
+        // and the edges are never removed
+        unsafe { self.edges.get_unchecked_mut(id.index) }
+    }
+
+    /// Returns a reference to the value of the given node
+    pub fn node(&self, id: NodeId<Tag>) -> &N {
+        &self.node_entry(id).value
+    }
+
+    /// Returns a mutable reference to the value of the given node
+    pub fn node_mut(&mut self, id: NodeId<Tag>) -> &mut N {
+        &mut self.node_entry_mut(id).value
+    }
+
+    /// Returns a reference to the value of the given edge
+    pub fn edge(&self, id: EdgeId<Tag>) -> &E {
+        &self.edge_entry(id).value
+    }
+
+    /// Returns a mutable reference to the value of the given edge
+    pub fn edge_mut(&mut self, id: EdgeId<Tag>) -> &mut E {
+        &mut self.edge_entry_mut(id).value
+    }
+
+    /// Returns the source and the target nodes of the given edge
+    pub fn endpoints(&self, id: EdgeId<Tag>) -> (NodeId<Tag>, NodeId<Tag>) {
+        let edge = self.edge_entry(id);
+        (edge.source, edge.target)
+    }
+
+    /// Returns an iterator over the edges going out of the given node
+    pub fn edges(&self, id: NodeId<Tag>) -> impl Iterator<Item = EdgeId<Tag>> + '_ {
+        self.node_entry(id).outgoing.iter().copied()
+    }
+
+    /// Returns an iterator over the targets of the edges going out of the given node
+    pub fn neighbors(&self, id: NodeId<Tag>) -> impl Iterator<Item = NodeId<Tag>> + '_ {
+        self.edges(id).map(|edge| self.edge_entry(edge).target)
+    }
+
+    /// Returns an iterator over the ids of all the nodes of the graph
+    pub fn node_ids(&self) -> impl Iterator<Item = NodeId<Tag>> {
+        (0..self.nodes.len()).map(NodeId::new)
+    }
+
+    /// Returns an iterator over the ids of all the edges of the graph
+    pub fn edge_ids(&self) -> impl Iterator<Item = EdgeId<Tag>> {
+        (0..self.edges.len()).map(EdgeId::new)
+    }
+
+    /// Returns the number of nodes in the graph
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns the number of edges in the graph
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+}
+
+impl<Tag: Unique, N, E> ops::Index<NodeId<Tag>> for Graph<Tag, N, E> {
+    type Output = N;
+
+    fn index(&self, id: NodeId<Tag>) -> &N {
+        self.node(id)
+    }
+}
+
+impl<Tag: Unique, N, E> ops::IndexMut<NodeId<Tag>> for Graph<Tag, N, E> {
+    fn index_mut(&mut self, id: NodeId<Tag>) -> &mut N {
+        self.node_mut(id)
+    }
+}
+
+impl<Tag: Unique, N, E> ops::Index<EdgeId<Tag>> for Graph<Tag, N, E> {
+    type Output = E;
+
+    fn index(&self, id: EdgeId<Tag>) -> &E {
+        self.edge(id)
+    }
+}
+
+impl<Tag: Unique, N, E> ops::IndexMut<EdgeId<Tag>> for Graph<Tag, N, E> {
+    fn index_mut(&mut self, id: EdgeId<Tag>) -> &mut E {
+        self.edge_mut(id)
+    }
+}
+
+impl<Tag: Unique, N: fmt::Debug, E: fmt::Debug> fmt::Debug for Graph<Tag, N, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let nodes = self.nodes.iter().map(|node| &node.value);
+        let edges = self.edges.iter().map(|edge| {
+            let (source, target) = (edge.source.index, edge.target.index);
+            (source, target, &edge.value)
+        });
+        f.debug_struct("Graph")
+            .field("nodes", &DebugIter(nodes))
+            .field("edges", &DebugIter(edges))
+            .finish()
+    }
+}
+
+/// Formats the items of a cloneable iterator as a list
+struct DebugIter<I>(I);
+
+impl<I: Iterator<Item = T> + Clone, T: fmt::Debug> fmt::Debug for DebugIter<I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.0.clone()).finish()
+    }
+}
+
+/// Defines an id of the graph tagged with the unique type `Tag`
+macro_rules! graph_id {
+    ($($(#[$attr:meta])* $name:ident;)*) => {$(
+        $(#[$attr])*
+        pub struct $name<Tag: Unique> {
+            index: usize,
+            _tag: PhantomData<Tag>,
+        }
+
+        impl<Tag: Unique> $name<Tag> {
+            const fn new(index: usize) -> Self {
+                Self {
+                    index,
+                    _tag: PhantomData,
+                }
+            }
+
+            /// Returns the position in insertion order of what this id refers to
+            pub const fn get(self) -> usize {
+                self.index
+            }
+        }
+
+        id_traits!($name<Tag>, |id| id.index);
+    )*};
+}
+
+graph_id! {
+    /// A node of the [`Graph`] tagged with the unique type `Tag`
+    ///
+    /// It can only be generated by the graph with the same tag, thus it always refers to a node.
+    NodeId;
+    /// An edge of the [`Graph`] tagged with the unique type `Tag`
+    ///
+    /// It can only be generated by the graph with the same tag, thus it always refers to an edge.
+    EdgeId;
+}
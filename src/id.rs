@@ -0,0 +1,64 @@
+use core::any::TypeId;
+
+use crate::Unique;
+
+/// An identifier of a unique type
+///
+/// Two identifiers are equal only if they belong to the same unique type,
+/// which makes it possible to compare and print tags at runtime.
+///
+/// Identifiers can only be generated for unique types that are `'static`,
+/// i.e. those generated from [`new!`](crate::new!), as the types generated from
+/// [`with_unique`](crate::with_unique) only differ in their lifetime
+/// which doesn't exist anymore once the program is compiled.
+///
+/// # Example
+///
+#[cfg_attr(feature = "nightly", doc = " ```")]
+#[cfg_attr(not(feature = "nightly"), doc = " ```ignore")]
+/// # fn main() {
+/// use unique_type::TagId;
+///
+/// type Tag = unique_type::new!();
+/// assert_eq!(TagId::of::<Tag>(), TagId::of::<Tag>());
+/// assert_ne!(TagId::of::<Tag>(), TagId::of::<unique_type::new!()>());
+/// # }
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct TagId(TypeId);
+
+impl TagId {
+    /// Returns the identifier of the unique type `Tag`
+    pub const fn of<Tag: Unique + 'static>() -> Self {
+        Self(TypeId::of::<Tag>())
+    }
+}
+
+/// Returns `true` if `A` and `B` are the same unique type
+///
+/// With the `nightly` feature this function can be evaluated at compile time.
+///
+/// # Example
+///
+#[cfg_attr(feature = "nightly", doc = " ```")]
+#[cfg_attr(not(feature = "nightly"), doc = " ```ignore")]
+/// # fn main() {
+/// type Tag = unique_type::new!();
+/// const SAME: bool = unique_type::same_tag::<Tag, Tag>();
+/// const DIFFERENT: bool = unique_type::same_tag::<Tag, unique_type::new!()>();
+/// assert!(SAME);
+/// assert!(!DIFFERENT);
+/// # }
+/// ```
+#[cfg(feature = "nightly")]
+pub const fn same_tag<A: Unique + 'static, B: Unique + 'static>() -> bool {
+    TagId::of::<A>().0 == TagId::of::<B>().0
+}
+
+/// Returns `true` if `A` and `B` are the same unique type
+///
+/// With the `nightly` feature this function can be evaluated at compile time.
+#[cfg(not(feature = "nightly"))]
+pub fn same_tag<A: Unique + 'static, B: Unique + 'static>() -> bool {
+    TagId::of::<A>() == TagId::of::<B>()
+}
@@ -0,0 +1,154 @@
+//! A string interner whose symbols are statically tied to it
+//!
+//! An [`Interner`] is tagged with a [`Unique`] type which is shared by all the
+//! [`Symbol`]s it generates, those can then only be resolved by the interner
+//! that generated them, without requiring any runtime check.
+//!
+//! # Example
+//!
+//! ```
+//! use unique_type::interner::Interner;
+//!
+//! unique_type::with_unique(|brand| {
+//!     let mut interner = Interner::new(brand);
+//!     let a = interner.intern("foo");
+//!     let b = interner.intern("bar");
+//!     assert_eq!(interner.intern("foo"), a);
+//!     assert_ne!(a, b);
+//!     assert_eq!(interner.resolve(b), "bar");
+//! });
+//! ```
+//!
+//! Resolving a symbol with an interner it doesn't belong to results in a compiler error:
+#![cfg_attr(feature = "nightly", doc = " ```compile_fail E0308")]
+#![cfg_attr(not(feature = "nightly"), doc = " ```ignore")]
+//! # fn main() {
+//! use unique_type::interner::Interner;
+//!
+//! let mut a = Interner::new(unique_type::new_value!());
+//! let b = Interner::new(unique_type::new_value!());
+//! let symbol = a.intern("foo");
+//! // a and b have two different tags
+//! b.resolve(symbol);
+//! # }
+//! ```
+//!
+//! And the same goes for types generated from [`with_unique`](crate::with_unique):
+//! ```compile_fail E0521
+//! use unique_type::{interner::Interner, with_unique};
+//!
+//! with_unique(|a| with_unique(|b| {
+//!     let mut a = Interner::new(a);
+//!     let b = Interner::new(b);
+//!     let symbol = a.intern("foo");
+//!     b.resolve(symbol);
+//! }));
+//! ```
+
+use alloc::boxed::Box;
+use core::{fmt, hash::BuildHasher};
+
+use hashbrown::{hash_table::Entry, DefaultHashBuilder, HashTable};
+
+use crate::{
+    vec::{BrandedVec, Index},
+    Brand, Unique,
+};
+
+/// A string interner tagged with the unique type `Tag`
+///
+/// The tag guarantees that a [`Symbol`] can only be resolved by the interner that created it,
+/// and since strings are never removed such a symbol always refers to one.
+///
+/// Each string is stored only once, the hash table used
+/// for looking them up only holds their indices.
+pub struct Interner<Tag: Unique> {
+    strings: BrandedVec<Tag, Box<str>>,
+    indices: HashTable<Index<Tag>>,
+    hasher: DefaultHashBuilder,
+}
+
+impl<Tag: Unique> Interner<Tag> {
+    /// Constructs a new empty interner tagged with `Tag`
+    ///
+    /// The brand is consumed, this guarantees that no other interner can have the same tag.
+    pub fn new(brand: Brand<Tag>) -> Self {
+        Self {
+            strings: BrandedVec::new(brand),
+            indices: HashTable::new(),
+            hasher: DefaultHashBuilder::default(),
+        }
+    }
+
+    /// Interns a string, returning its symbol
+    ///
+    /// Interning the same string more than once always returns the same symbol.
+    pub fn intern(&mut self, string: &str) -> Symbol<Tag> {
+        let Self {
+            strings,
+            indices,
+            hasher,
+        } = self;
+        let hash = hasher.hash_one(string);
+        let entry = indices.entry(
+            hash,
+            |&index| *strings[index] == *string,
+            |&index| hasher.hash_one(&*strings[index]),
+        );
+        match entry {
+            Entry::Occupied(entry) => Symbol(*entry.get()),
+            Entry::Vacant(entry) => {
+                let index = strings.push(string.into());
+                entry.insert(index);
+                Symbol(index)
+            }
+        }
+    }
+
+    /// Returns the symbol of `string`,
+    /// or [`None`] if it was never interned
+    pub fn get(&self, string: &str) -> Option<Symbol<Tag>> {
+        let hash = self.hasher.hash_one(string);
+        self.indices
+            .find(hash, |&index| *self.strings[index] == *string)
+            .map(|&index| Symbol(index))
+    }
+
+    /// Returns the string the symbol refers to
+    pub fn resolve(&self, symbol: Symbol<Tag>) -> &str {
+        &self.strings[symbol.0]
+    }
+
+    /// Returns the number of interned strings
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    /// Returns `true` if no string was interned
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+
+    /// Returns an iterator over the symbols and the strings they refer to,
+    /// in interning order
+    pub fn iter(&self) -> impl Iterator<Item = (Symbol<Tag>, &str)> {
+        self.strings
+            .indices()
+            .map(|index| (Symbol(index), &*self.strings[index]))
+    }
+}
+
+impl<Tag: Unique> fmt::Debug for Interner<Tag> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set()
+            .entries(self.iter().map(|(_, string)| string))
+            .finish()
+    }
+}
+
+/// A string interned by the [`Interner`] tagged with the unique type `Tag`
+///
+/// It can only be generated by the interner with the same tag, thus it always refers to a string.
+pub struct Symbol<Tag: Unique>(Index<Tag>);
+
+id_traits!(Symbol<Tag>, |symbol| symbol.0.get());
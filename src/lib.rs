@@ -21,7 +21,8 @@
 //! ```
 //! Then when a value of that type is used, the [`new!`] macro can be used to declare the
 //! unique type for that value:
-//! ```
+#![cfg_attr(feature = "nightly", doc = " ```")]
+#![cfg_attr(not(feature = "nightly"), doc = " ```ignore")]
 //! # use core::marker::PhantomData;
 //! # struct Struct<Tag: unique_type::Unique> { _marker: PhantomData<Tag> }
 //! # impl<Tag: unique_type::Unique> Struct<Tag> {
@@ -44,7 +45,8 @@
 //! ```
 //! Now calling this function with two values that don't have the same tag will result in
 //! a compiler error:
-//! ```compile_fail E0308
+#![cfg_attr(feature = "nightly", doc = " ```compile_fail E0308")]
+#![cfg_attr(not(feature = "nightly"), doc = " ```ignore")]
 //! # struct Struct<Tag: unique_type::Unique> { _marker: core::marker::PhantomData<Tag> }
 //! # fn foo<Tag: unique_type::Unique>(a: Struct<Tag>, b: Struct<Tag>) { todo!() }
 //! # fn main() {
@@ -54,38 +56,139 @@
 //! foo(a, b)
 //! # }
 //! ```
+//!
+//! # Stable toolchain
+//!
+//! The [`new!`] macro requires a nightly toolchain and is only available
+//! with the `nightly` feature (enabled by default).
+//!
+//! On the stable toolchain [`with_unique`] can be used instead, it calls a closure
+//! with the [`Brand`] of a unique type that can't escape from it:
+//! ```
+//! # struct Struct<Tag: unique_type::Unique> { _marker: core::marker::PhantomData<Tag> }
+//! # impl<Tag: unique_type::Unique> Struct<Tag> {
+//! #     fn new(_: &unique_type::Brand<Tag>) -> Self { Self { _marker: core::marker::PhantomData } }
+//! # }
+//! unique_type::with_unique(|brand| {
+//!     let value = Struct::new(&brand);
+//!     // ...
+//! });
+//! ```
+//!
+//! # Collections
+//!
+//! The branded collections ([`vec`], [`arena`], [`map`], [`interner`] and [`graph`]) allocate on the heap,
+//! thus they are only available with the `alloc` feature (enabled by `std`, which is
+//! a default feature). Everything else only depends on `core`.
 
 // Required for having &str as a const generic
-#![feature(adt_const_params)]
-#![feature(const_type_id)]
+#![cfg_attr(feature = "nightly", feature(adt_const_params))]
+#![cfg_attr(feature = "nightly", feature(const_type_id))]
+// Required for comparing type-ids at compile time
+#![cfg_attr(feature = "nightly", feature(const_trait_impl, const_cmp))]
 #![no_std]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "nightly")]
 use core::any::TypeId;
 
+#[macro_use]
+mod macros;
+
+#[cfg(any(feature = "portable-atomic", target_has_atomic = "64"))]
+mod allocator;
+#[cfg(feature = "alloc")]
+pub mod arena;
+mod brand;
+pub mod capability;
+pub mod cell;
+mod child;
+mod distinct;
+mod dyn_tag;
+#[cfg(feature = "alloc")]
+pub mod graph;
+mod id;
+#[cfg(feature = "alloc")]
+pub mod interner;
+pub mod list;
+#[cfg(feature = "alloc")]
+pub mod map;
+mod origin;
+pub mod quantity;
+mod scoped;
+pub mod slice;
+pub mod state;
+mod tagged;
+#[cfg(feature = "alloc")]
+pub mod vec;
+
+#[cfg(any(feature = "portable-atomic", target_has_atomic = "64"))]
+pub use allocator::TagAllocator;
+pub use brand::Brand;
+pub use child::{Child, SubTagOf};
+pub use distinct::Distinct;
+pub use dyn_tag::{DynId, DynTag, DynTagged};
+pub use id::{same_tag, TagId};
+pub use origin::Origin;
+pub use scoped::{with_unique, Scoped};
+pub use tagged::Tagged;
+#[cfg(feature = "derive")]
+pub use unique_type_derive::branded;
+
 mod pvt {
     /// Private version of [`Unique`](super::Unique)
     ///
     /// This seals the trait so that it cannot be implemented outside of the crate
-    pub trait Unique {}
+    pub trait Unique {
+        /// See [`Unique::origin`](super::Unique::origin)
+        const ORIGIN: Option<super::Origin> = None;
+        /// See [`Unique::LABEL`](super::Unique::LABEL)
+        const TAG_LABEL: Option<&'static str> = None;
+    }
 }
 
 /// An interface for reqiring unique types
 ///
-/// The only types implementing this trait are the ones generated from [`new!`].
-pub trait Unique: pvt::Unique {}
+/// The only types implementing this trait are the ones generated from [`new!`]
+/// and [`with_unique`], and the ones composed from them in the [`quantity`] module
+/// or as a [`Child`] of another unique type.
+///
+/// Values of these types can't be constructed, the ownership of
+/// a unique type is instead proven by its [`Brand`].
+pub trait Unique: pvt::Unique {
+    /// The label given to this type when it was generated, if any
+    ///
+    /// See the documentation of [`new!`] for how to label a type.
+    const LABEL: Option<&'static str> = Self::TAG_LABEL;
+
+    /// Returns the location where this type was generated
+    ///
+    /// This is [`None`] for the types generated from [`with_unique`],
+    /// as they are only distinguished by their lifetime.
+    fn origin() -> Option<Origin> {
+        Self::ORIGIN
+    }
+}
 
 impl<T: pvt::Unique> Unique for T {}
 
 /// A set of values that can only be constructed through the
 /// [`Set::unique`] function
+#[cfg(feature = "nightly")]
 #[doc(hidden)]
 #[derive(PartialEq, Eq)]
-pub struct Set(TypeId);
+pub struct Set(TypeId, Origin, &'static str);
 
+#[cfg(feature = "nightly")]
 impl Set {
     /// Constructs a new set of values that are unique from any other
     /// generated with this function
     ///
+    /// The `origin` is the location where the set is constructed and the `label`
+    /// is the name given to it (empty if there is none), both are only used for diagnostics.
+    ///
     /// # Safety
     ///
     /// This function is safe only if `T` is a unique and anonymous type.
@@ -93,8 +196,8 @@ impl Set {
     /// For example, this is a valid usage:
     /// ```
     /// # fn main() { unsafe {
-    /// # use unique_type::Set;
-    /// Set::unique(&(|| {}));
+    /// # use unique_type::{Origin, Set};
+    /// Set::unique(&(|| {}), Origin::new(file!(), line!(), column!()), "");
     /// # } }
     /// ```
     /// because from the [Rust Reference](https://doc.rust-lang.org/reference/types/closure.html):
@@ -104,13 +207,13 @@ impl Set {
     /// While this is an unsafe usage:
     /// ```
     /// # fn main() { unsafe {
-    /// # use unique_type::Set;
-    /// Set::unique(&0usize);
+    /// # use unique_type::{Origin, Set};
+    /// Set::unique(&0usize, Origin::new(file!(), line!(), column!()), "");
     /// # } }
     /// ```
     /// because the usize type can be named
-    pub const unsafe fn unique<T>(_: &'static T) -> Self {
-        Self(TypeId::of::<T>())
+    pub const unsafe fn unique<T>(_: &'static T, origin: Origin, label: &'static str) -> Self {
+        Self(TypeId::of::<T>(), origin, label)
     }
 }
 
@@ -119,18 +222,27 @@ impl Set {
 /// The uniqueness of this type is based on the [`Set`] type which
 /// can only be safely constructed with unique values thus making every
 /// "specialization" of this type generate a different type-id
+#[cfg(feature = "nightly")]
 #[doc(hidden)]
 pub struct Template<const T: Set>(());
 
-impl<const T: Set> pvt::Unique for Template<T> {}
+#[cfg(feature = "nightly")]
+impl<const T: Set> pvt::Unique for Template<T> {
+    const ORIGIN: Option<Origin> = Some(T.1);
+    const TAG_LABEL: Option<&'static str> = if T.2.is_empty() { None } else { Some(T.2) };
+}
 
 /// Generates a unique type that implements the [`Unique`] trait
 ///
+/// The generated type can be named through type aliases and used anywhere,
+/// when it must not outlive a specific context use [`with_unique`] instead.
+///
 /// # Example
 ///
 /// Calling this macro twice will always generate two different types,
 /// thus this will panic:
-/// ```should_panic
+#[cfg_attr(feature = "nightly", doc = " ```should_panic")]
+#[cfg_attr(not(feature = "nightly"), doc = " ```ignore")]
 /// # fn main() {
 /// # use core::any::TypeId;
 /// assert_eq!(
@@ -141,18 +253,128 @@ impl<const T: Set> pvt::Unique for Template<T> {}
 /// ```
 ///
 /// And this won't even compile:
-/// ```compile_fail E0308
+#[cfg_attr(feature = "nightly", doc = " ```compile_fail E0308")]
+#[cfg_attr(not(feature = "nightly"), doc = " ```ignore")]
 /// # fn main() {
 /// let a: unique_type::new!() = todo!();
 /// let b: unique_type::new!() = a;
 /// # }
 /// ```
+///
+/// # Labels
+///
+/// A string literal or an identifier can be passed to the macro to label the
+/// generated type, the label is then available through [`Unique::LABEL`]
+/// and it's shown in the compiler errors and in the [`Debug`] output of [`Brand`]:
+#[cfg_attr(feature = "nightly", doc = " ```")]
+#[cfg_attr(not(feature = "nightly"), doc = " ```ignore")]
+/// # fn main() {
+/// use unique_type::Unique;
+///
+/// type Pool = unique_type::new!("db-pool");
+/// type Session = unique_type::new!(Session);
+/// assert_eq!(Pool::LABEL, Some("db-pool"));
+/// assert_eq!(Session::LABEL, Some("Session"));
+/// # }
+/// ```
+///
+/// Labels don't affect the uniqueness of the generated types:
+#[cfg_attr(feature = "nightly", doc = " ```compile_fail E0308")]
+#[cfg_attr(not(feature = "nightly"), doc = " ```ignore")]
+/// # fn main() {
+/// let a: unique_type::new!("label") = todo!();
+/// let b: unique_type::new!("label") = a;
+/// # }
+/// ```
+#[cfg(feature = "nightly")]
 #[macro_export]
 macro_rules! new {
     () => {
+        $crate::new!(@ "")
+    };
+    ($label:literal) => {
+        $crate::new!(@ $label)
+    };
+    ($label:ident) => {
+        $crate::new!(@ ::core::stringify!($label))
+    };
+    (@ $label:expr) => {
         $crate::Template<{
             // SAFETY: the const generics values are the one stated in the docs for Set
-            unsafe { $crate::Set::unique(&(||{})) }
+            unsafe {
+                $crate::Set::unique(
+                    &(||{}),
+                    $crate::Origin::new(::core::file!(), ::core::line!(), ::core::column!()),
+                    $label,
+                )
+            }
         }>
     };
 }
+
+/// Generates a unique type that implements the [`Unique`] trait and returns its [`Brand`]
+///
+/// Differently from [`new!`], the type generated by this macro can't be reused through
+/// type aliases or associated types to tag multiple values, as the only way of
+/// obtaining it is together with the single brand this macro returns.
+///
+/// The generated type can be labelled just like with [`new!`].
+///
+/// # Panics
+///
+/// Panics if the same expansion of the macro is evaluated more than once,
+/// for example inside of a loop or of a generic function called with different types.
+///
+/// # Example
+///
+/// The generated brand can be used to prove that nothing else has the same tag:
+#[cfg_attr(all(feature = "nightly", feature = "alloc"), doc = " ```")]
+#[cfg_attr(not(all(feature = "nightly", feature = "alloc")), doc = " ```ignore")]
+/// # fn main() {
+/// let brand = unique_type::new_value!();
+/// let vec = unique_type::vec::BrandedVec::<_, usize>::new(brand);
+/// # }
+/// ```
+///
+/// Calling this macro twice will always generate two different types,
+/// thus this won't compile:
+#[cfg_attr(feature = "nightly", doc = " ```compile_fail E0308")]
+#[cfg_attr(not(feature = "nightly"), doc = " ```ignore")]
+/// # fn main() {
+/// let a = unique_type::new_value!();
+/// let b = unique_type::new_value!();
+/// let b = if true { a } else { b };
+/// # }
+/// ```
+///
+/// And aliasing a type generated from [`new!`] won't help:
+#[cfg_attr(feature = "nightly", doc = " ```compile_fail E0308")]
+#[cfg_attr(not(feature = "nightly"), doc = " ```ignore")]
+/// # fn main() {
+/// type Alias = unique_type::new!();
+/// let brand: unique_type::Brand<Alias> = unique_type::new_value!();
+/// # }
+/// ```
+///
+/// While evaluating the same expansion twice will panic:
+#[cfg_attr(feature = "nightly", doc = " ```should_panic")]
+#[cfg_attr(not(feature = "nightly"), doc = " ```ignore")]
+/// # fn main() {
+/// for _ in 0..2 {
+///     let brand = unique_type::new_value!();
+/// }
+/// # }
+/// ```
+#[cfg(feature = "nightly")]
+#[macro_export]
+macro_rules! new_value {
+    ($($label:tt)?) => {{
+        static TAKEN: ::core::sync::atomic::AtomicBool =
+            ::core::sync::atomic::AtomicBool::new(false);
+        if TAKEN.swap(true, ::core::sync::atomic::Ordering::Relaxed) {
+            ::core::panic!("the brand of a unique type can only be generated once");
+        }
+        // SAFETY: the check above guarantees that this is evaluated only once
+        unsafe { $crate::Brand::<$crate::new!($($label)?)>::new_unchecked() }
+    }};
+}
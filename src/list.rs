@@ -0,0 +1,139 @@
+//! Type-level lists of unique types
+//!
+//! A list is built with the [`TagList!`](crate::TagList!) macro and the [`Member`] trait
+//! is implemented by the unique types it contains, thus a function can accept
+//! values tagged with any type of a fixed group and the compiler checks the membership.
+//!
+//! The second parameter of [`Member`] is the witness of the position of the type in the list,
+//! it's always inferred by the compiler and it can be read through [`Member::INDEX`].
+//!
+//! # Example
+//!
+//! ```
+//! use unique_type::{list::Member, with_unique, TagList, Tagged, Unique};
+//!
+//! fn position<List, X: Member<List, I>, I>(_: &Tagged<X, u32>) -> usize {
+//!     X::INDEX
+//! }
+//!
+//! fn positions<A: Unique, B: Unique>(a: &Tagged<A, u32>, b: &Tagged<B, u32>) -> [usize; 2] {
+//!     [
+//!         position::<TagList![A, B], _, _>(a),
+//!         position::<TagList![A, B], _, _>(b),
+//!     ]
+//! }
+//!
+//! with_unique(|a| with_unique(|b| {
+//!     assert_eq!(positions(&Tagged::new(&a, 1), &Tagged::new(&b, 2)), [0, 1]);
+//! }));
+//! ```
+//!
+//! Using a type that is not in the list results in a compiler error:
+//! ```compile_fail E0277
+//! use unique_type::{list::Member, TagList, Tagged, Unique};
+//!
+//! fn position<List, X: Member<List, I>, I>(_: &Tagged<X, u32>) -> usize {
+//!     X::INDEX
+//! }
+//!
+//! fn positions<A: Unique, B: Unique>(a: &Tagged<A, u32>, b: &Tagged<B, u32>) -> [usize; 2] {
+//!     [position::<TagList![A], _, _>(a), position::<TagList![A], _, _>(b)]
+//! }
+//! ```
+
+use core::marker::PhantomData;
+
+use crate::Unique;
+
+mod pvt {
+    /// Private version of [`TagList`](super::TagList)
+    pub trait TagList {}
+
+    /// Private version of [`Position`](super::Position)
+    pub trait Position {}
+
+    /// Private version of [`Member`](super::Member)
+    pub trait Member<List, I> {}
+}
+
+/// A type-level list of unique types
+///
+/// This trait is sealed and implemented only by [`Nil`] and [`Cons`].
+pub trait TagList: pvt::TagList {}
+
+impl<T: pvt::TagList> TagList for T {}
+
+/// The empty list
+pub struct Nil;
+
+impl pvt::TagList for Nil {}
+
+/// The list starting with `Head` and continuing with `Tail`
+pub struct Cons<Head: Unique, Tail: TagList>(PhantomData<(Head, Tail)>);
+
+impl<Head: Unique, Tail: TagList> pvt::TagList for Cons<Head, Tail> {}
+
+/// The witness of the position of a type in a list
+///
+/// This trait is sealed and implemented only by [`Here`] and [`There`].
+pub trait Position: pvt::Position {}
+
+impl<T: pvt::Position> Position for T {}
+
+/// The position of the first type of a list
+pub struct Here;
+
+impl pvt::Position for Here {}
+
+/// The position following `I`
+pub struct There<I: Position>(PhantomData<I>);
+
+impl<I: Position> pvt::Position for There<I> {}
+
+/// An interface for requiring a unique type to be in `List`,
+/// at the position witnessed by `I`
+///
+/// This trait is sealed and implemented only for the types in a [`TagList`].
+pub trait Member<List, I>: Unique + pvt::Member<List, I> {
+    /// The position of the type in the list, starting from zero
+    const INDEX: usize;
+}
+
+impl<Tag: Unique, Tail: TagList> pvt::Member<Cons<Tag, Tail>, Here> for Tag {}
+
+impl<Tag: Unique, Tail: TagList> Member<Cons<Tag, Tail>, Here> for Tag {
+    const INDEX: usize = 0;
+}
+
+impl<Tag, Head, Tail, I> pvt::Member<Cons<Head, Tail>, There<I>> for Tag
+where
+    Tag: Member<Tail, I>,
+    Head: Unique,
+    Tail: TagList,
+    I: Position,
+{
+}
+
+impl<Tag, Head, Tail, I> Member<Cons<Head, Tail>, There<I>> for Tag
+where
+    Tag: Member<Tail, I>,
+    Head: Unique,
+    Tail: TagList,
+    I: Position,
+{
+    const INDEX: usize = <Tag as Member<Tail, I>>::INDEX + 1;
+}
+
+/// Builds a type-level list of unique types
+///
+/// `TagList![A, B, C]` expands to `Cons<A, Cons<B, Cons<C, Nil>>>`,
+/// see the [`list`](crate::list) module for more details.
+#[macro_export]
+macro_rules! TagList {
+    () => {
+        $crate::list::Nil
+    };
+    ($head:ty $(, $tail:ty)* $(,)?) => {
+        $crate::list::Cons<$head, $crate::TagList![$($tail),*]>
+    };
+}
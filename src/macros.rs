@@ -0,0 +1,65 @@
+/// Implements the common traits for an id tagged with the unique type `Tag`
+///
+/// The traits are implemented manually as deriving them would require `Tag` (and the other
+/// type parameters) to implement them too. The id is compared, hashed and formatted
+/// by the value of `$key`, unless a custom `Debug` implementation is given.
+macro_rules! id_traits {
+    (
+        $name:ident<Tag $(, $param:ident)*>, |$id:ident| $key:expr,
+        Debug(|$this:ident, $f:ident| $fmt:expr)
+    ) => {
+        impl<Tag: $crate::Unique $(, $param)*> Clone for $name<Tag $(, $param)*> {
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+
+        impl<Tag: $crate::Unique $(, $param)*> Copy for $name<Tag $(, $param)*> {}
+
+        impl<Tag: $crate::Unique $(, $param)*> $name<Tag $(, $param)*> {
+            fn id_key(self) -> impl Ord + core::hash::Hash + core::fmt::Debug {
+                let $id = self;
+                $key
+            }
+        }
+
+        impl<Tag: $crate::Unique $(, $param)*> PartialEq for $name<Tag $(, $param)*> {
+            fn eq(&self, other: &Self) -> bool {
+                self.id_key() == other.id_key()
+            }
+        }
+
+        impl<Tag: $crate::Unique $(, $param)*> Eq for $name<Tag $(, $param)*> {}
+
+        impl<Tag: $crate::Unique $(, $param)*> PartialOrd for $name<Tag $(, $param)*> {
+            fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl<Tag: $crate::Unique $(, $param)*> Ord for $name<Tag $(, $param)*> {
+            fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+                self.id_key().cmp(&other.id_key())
+            }
+        }
+
+        impl<Tag: $crate::Unique $(, $param)*> core::hash::Hash for $name<Tag $(, $param)*> {
+            fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+                self.id_key().hash(state)
+            }
+        }
+
+        impl<Tag: $crate::Unique $(, $param)*> core::fmt::Debug for $name<Tag $(, $param)*> {
+            fn fmt(&self, $f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                let $this = self;
+                $fmt
+            }
+        }
+    };
+    ($name:ident<Tag $(, $param:ident)*>, |$id:ident| $key:expr) => {
+        id_traits!(
+            $name<Tag $(, $param)*>, |$id| $key,
+            Debug(|id, f| f.debug_tuple(stringify!($name)).field(&id.id_key()).finish())
+        );
+    };
+}
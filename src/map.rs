@@ -0,0 +1,192 @@
+//! A map whose keys are statically tied to it
+//!
+//! A [`BrandedMap`] is tagged with a [`Unique`] type which is shared by all the
+//! [`Key`]s it generates, those prove that an entry is in the map, so looking it up
+//! can't fail and doesn't require searching for it.
+//!
+//! # Example
+//!
+//! ```
+//! use unique_type::map::BrandedMap;
+//!
+//! unique_type::with_unique(|brand| {
+//!     let mut map = BrandedMap::new(brand);
+//!     let (alice, _) = map.insert("alice", 1);
+//!     let (bob, _) = map.insert("bob", 2);
+//!     assert_eq!(map.insert("alice", 3), (alice, Some(1)));
+//!     assert_eq!(map[alice], 3);
+//!     assert_eq!(map.find("bob"), Some(bob));
+//!     assert_eq!(map.key(bob), &"bob");
+//! });
+//! ```
+//!
+//! Using a key with a map it doesn't belong to results in a compiler error:
+#![cfg_attr(feature = "nightly", doc = " ```compile_fail E0308")]
+#![cfg_attr(not(feature = "nightly"), doc = " ```ignore")]
+//! # fn main() {
+//! use unique_type::map::BrandedMap;
+//!
+//! let mut a = BrandedMap::new(unique_type::new_value!());
+//! let b = BrandedMap::<_, &str, usize>::new(unique_type::new_value!());
+//! let (key, _) = a.insert("key", 0);
+//! // a and b have two different tags
+//! b[key];
+//! # }
+//! ```
+//!
+//! And the same goes for types generated from [`with_unique`](crate::with_unique):
+//! ```compile_fail E0521
+//! use unique_type::{map::BrandedMap, with_unique};
+//!
+//! with_unique(|a| with_unique(|b| {
+//!     let mut a = BrandedMap::new(a);
+//!     let b = BrandedMap::<_, &str, usize>::new(b);
+//!     let (key, _) = a.insert("key", 0);
+//!     b[key];
+//! }));
+//! ```
+
+use core::{
+    borrow::Borrow,
+    fmt,
+    hash::{BuildHasher, Hash},
+    mem, ops,
+};
+
+use hashbrown::{hash_table::Entry, DefaultHashBuilder, HashTable};
+
+use crate::{
+    vec::{BrandedVec, Index},
+    Brand, Unique,
+};
+
+/// An append-only hash map tagged with the unique type `Tag`
+///
+/// The tag guarantees that a [`Key`] can only be used with the map that created it,
+/// and since entries can never be removed such a key always refers to an entry.
+///
+/// The entries are kept in insertion order and each key is stored only once,
+/// the hash table used for looking them up only holds their indices.
+pub struct BrandedMap<Tag: Unique, K, V> {
+    entries: BrandedVec<Tag, (K, V)>,
+    indices: HashTable<Index<Tag>>,
+    hasher: DefaultHashBuilder,
+}
+
+impl<Tag: Unique, K, V> BrandedMap<Tag, K, V> {
+    /// Constructs a new empty map tagged with `Tag`
+    ///
+    /// The brand is consumed, this guarantees that no other map can have the same tag.
+    pub fn new(brand: Brand<Tag>) -> Self {
+        Self {
+            entries: BrandedVec::new(brand),
+            indices: HashTable::new(),
+            hasher: DefaultHashBuilder::default(),
+        }
+    }
+
+    /// Returns a reference to the value of the given key
+    pub fn get(&self, key: Key<Tag>) -> &V {
+        &self.entries[key.0].1
+    }
+
+    /// Returns a mutable reference to the value of the given key
+    pub fn get_mut(&mut self, key: Key<Tag>) -> &mut V {
+        &mut self.entries[key.0].1
+    }
+
+    /// Returns a reference to the actual key proven by the given one
+    pub fn key(&self, key: Key<Tag>) -> &K {
+        &self.entries[key.0].0
+    }
+
+    /// Returns the number of entries in the map
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the map contains no entries
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns an iterator over the entries of the map, in insertion order
+    pub fn iter(&self) -> impl Iterator<Item = (Key<Tag>, &K, &V)> {
+        self.entries.indices().map(|index| {
+            let (key, value) = &self.entries[index];
+            (Key(index), key, value)
+        })
+    }
+}
+
+impl<Tag: Unique, K: Hash + Eq, V> BrandedMap<Tag, K, V> {
+    /// Inserts a value in the map, returning the proof that its key is in the map
+    ///
+    /// If the map already contains the key the value is replaced and the old one is returned.
+    pub fn insert(&mut self, key: K, value: V) -> (Key<Tag>, Option<V>) {
+        let Self {
+            entries,
+            indices,
+            hasher,
+        } = self;
+        let hash = hasher.hash_one(&key);
+        let entry = indices.entry(
+            hash,
+            |&index| entries[index].0 == key,
+            |&index| hasher.hash_one(&entries[index].0),
+        );
+        match entry {
+            Entry::Occupied(entry) => {
+                let index = *entry.get();
+                let old = mem::replace(&mut entries[index].1, value);
+                (Key(index), Some(old))
+            }
+            Entry::Vacant(entry) => {
+                let index = entries.push((key, value));
+                entry.insert(index);
+                (Key(index), None)
+            }
+        }
+    }
+
+    /// Returns the proof that `key` is in the map,
+    /// or [`None`] if it isn't
+    pub fn find<Q: Hash + Eq + ?Sized>(&self, key: &Q) -> Option<Key<Tag>>
+    where
+        K: Borrow<Q>,
+    {
+        let hash = self.hasher.hash_one(key);
+        self.indices
+            .find(hash, |&index| self.entries[index].0.borrow() == key)
+            .map(|&index| Key(index))
+    }
+}
+
+impl<Tag: Unique, K, V> ops::Index<Key<Tag>> for BrandedMap<Tag, K, V> {
+    type Output = V;
+
+    fn index(&self, key: Key<Tag>) -> &V {
+        self.get(key)
+    }
+}
+
+impl<Tag: Unique, K, V> ops::IndexMut<Key<Tag>> for BrandedMap<Tag, K, V> {
+    fn index_mut(&mut self, key: Key<Tag>) -> &mut V {
+        self.get_mut(key)
+    }
+}
+
+impl<Tag: Unique, K: fmt::Debug, V: fmt::Debug> fmt::Debug for BrandedMap<Tag, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(self.iter().map(|(_, key, value)| (key, value)))
+            .finish()
+    }
+}
+
+/// The proof that an entry is in the [`BrandedMap`] tagged with the unique type `Tag`
+///
+/// It can only be generated by the map with the same tag, thus it always refers to an entry.
+pub struct Key<Tag: Unique>(Index<Tag>);
+
+id_traits!(Key<Tag>, |key| key.0.get());
@@ -0,0 +1,56 @@
+use core::fmt;
+
+/// The location in the source code where a unique type was generated
+///
+/// It's returned by [`Unique::origin`](crate::Unique::origin) and it's
+/// useful for finding out which expansion of [`new!`](crate::new!) produced a tag.
+///
+/// # Example
+///
+#[cfg_attr(feature = "nightly", doc = " ```")]
+#[cfg_attr(not(feature = "nightly"), doc = " ```ignore")]
+/// # fn main() {
+/// use unique_type::Unique;
+///
+/// type Tag = unique_type::new!();
+/// let origin = Tag::origin().unwrap();
+/// assert_eq!(origin.file(), file!());
+/// assert_eq!(origin.line(), line!() - 3);
+/// # }
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Origin {
+    file: &'static str,
+    line: u32,
+    column: u32,
+}
+
+impl Origin {
+    /// Constructs a new origin, this is meant to be used
+    /// with the [`file!`], [`line!`] and [`column!`] macros
+    #[doc(hidden)]
+    pub const fn new(file: &'static str, line: u32, column: u32) -> Self {
+        Self { file, line, column }
+    }
+
+    /// Returns the name of the source file
+    pub const fn file(&self) -> &'static str {
+        self.file
+    }
+
+    /// Returns the line number in the source file
+    pub const fn line(&self) -> u32 {
+        self.line
+    }
+
+    /// Returns the column number in the source file
+    pub const fn column(&self) -> u32 {
+        self.column
+    }
+}
+
+impl fmt::Display for Origin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.file, self.line, self.column)
+    }
+}
@@ -0,0 +1,216 @@
+//! Units-of-measure style arithmetic over unique types
+//!
+//! A [`Quantity`] is a value whose unique tag acts as its unit of measure:
+//! quantities can only be added to or subtracted from quantities with the same unit,
+//! while multiplying or dividing them produces a quantity whose unit is the
+//! [`Product`] or the [`Quotient`] of the two.
+//!
+//! Compound units are compared structurally, thus `Product<A, B>` and `Product<B, A>`
+//! are different units, as well as `Quotient<A, A>` and a dimensionless value.
+//!
+//! # Example
+//!
+//! ```
+//! use unique_type::{quantity::{Product, Quantity, Quotient}, Unique};
+//!
+//! fn area<M: Unique>(w: Quantity<M, f64>, h: Quantity<M, f64>) -> Quantity<Product<M, M>, f64> {
+//!     w * h
+//! }
+//!
+//! unique_type::with_unique(|meters| unique_type::with_unique(|seconds| {
+//!     let distance = Quantity::new(&meters, 10.0) + Quantity::new(&meters, 20.0);
+//!     let time = Quantity::new(&seconds, 3.0);
+//!     let speed = distance / time;
+//!     assert_eq!(speed.into_inner(), 10.0);
+//!     assert_eq!(area(distance, distance).into_inner(), 900.0);
+//! }));
+//! ```
+//!
+//! Adding quantities with different units results in a compiler error:
+#![cfg_attr(feature = "nightly", doc = " ```compile_fail E0308")]
+#![cfg_attr(not(feature = "nightly"), doc = " ```ignore")]
+//! # fn main() {
+//! use unique_type::quantity::Quantity;
+//!
+//! let meters = Quantity::new(&unique_type::new_value!(), 1.0);
+//! let seconds = Quantity::new(&unique_type::new_value!(), 1.0);
+//! meters + seconds;
+//! # }
+//! ```
+//!
+//! And the same goes for compound units:
+#![cfg_attr(feature = "nightly", doc = " ```compile_fail E0308")]
+#![cfg_attr(not(feature = "nightly"), doc = " ```ignore")]
+//! # fn main() {
+//! use unique_type::quantity::Quantity;
+//!
+//! let meters = Quantity::new(&unique_type::new_value!(), 1.0);
+//! let seconds = Quantity::new(&unique_type::new_value!(), 1.0);
+//! meters * seconds + meters;
+//! # }
+//! ```
+//!
+//! Including units generated from [`with_unique`](crate::with_unique):
+//! ```compile_fail E0521
+//! use unique_type::{quantity::Quantity, with_unique};
+//!
+//! with_unique(|a| with_unique(|b| {
+//!     Quantity::new(&a, 1.0) + Quantity::new(&b, 1.0);
+//! }));
+//! ```
+
+use core::{cmp, fmt, hash, marker::PhantomData, ops};
+
+use crate::{pvt, Brand, Tagged, Unique};
+
+/// The unique type obtained by multiplying quantities with the units `A` and `B`
+pub struct Product<A: Unique, B: Unique>(PhantomData<(A, B)>);
+
+impl<A: Unique, B: Unique> pvt::Unique for Product<A, B> {}
+
+/// The unique type obtained by dividing quantities with the units `A` and `B`
+pub struct Quotient<A: Unique, B: Unique>(PhantomData<(A, B)>);
+
+impl<A: Unique, B: Unique> pvt::Unique for Quotient<A, B> {}
+
+/// A value of type `T` measured in the unit `Tag`
+///
+/// It's a [`Tagged`] value with different rules for multiplication and division.
+#[repr(transparent)]
+pub struct Quantity<Tag: Unique, T>(Tagged<Tag, T>);
+
+impl<Tag: Unique, T> Quantity<Tag, T> {
+    /// Constructs a quantity measured in the unit of the given brand
+    pub const fn new(brand: &Brand<Tag>, value: T) -> Self {
+        Self(Tagged::new(brand, value))
+    }
+
+    /// Consumes the quantity, returning its value
+    pub fn into_inner(self) -> T {
+        self.0.into_inner()
+    }
+
+    /// Returns a reference to the value of the quantity
+    pub fn value(&self) -> &T {
+        &self.0
+    }
+
+    /// Maps the value to another one with the same unit
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Quantity<Tag, U> {
+        Quantity(self.0.map(f))
+    }
+
+    /// Converts the quantity into a tagged value
+    pub fn into_tagged(self) -> Tagged<Tag, T> {
+        self.0
+    }
+}
+
+impl<Tag: Unique, T> From<Tagged<Tag, T>> for Quantity<Tag, T> {
+    fn from(value: Tagged<Tag, T>) -> Self {
+        Self(value)
+    }
+}
+
+impl<A: Unique, B: Unique, T: ops::Mul<U>, U> ops::Mul<Quantity<B, U>> for Quantity<A, T> {
+    type Output = Quantity<Product<A, B>, T::Output>;
+
+    fn mul(self, rhs: Quantity<B, U>) -> Self::Output {
+        let value = self.into_inner() * rhs.into_inner();
+        Quantity(Tagged::from_inner(value))
+    }
+}
+
+impl<A: Unique, B: Unique, T: ops::Div<U>, U> ops::Div<Quantity<B, U>> for Quantity<A, T> {
+    type Output = Quantity<Quotient<A, B>, T::Output>;
+
+    fn div(self, rhs: Quantity<B, U>) -> Self::Output {
+        let value = self.into_inner() / rhs.into_inner();
+        Quantity(Tagged::from_inner(value))
+    }
+}
+
+/// Implements an operator (and its assigning version)
+/// between two quantities with the same unit
+macro_rules! same_unit_op {
+    ($($op:ident::$fn:ident, $op_assign:ident::$fn_assign:ident;)*) => {$(
+        impl<Tag: Unique, T: ops::$op<U>, U> ops::$op<Quantity<Tag, U>> for Quantity<Tag, T> {
+            type Output = Quantity<Tag, T::Output>;
+
+            fn $fn(self, rhs: Quantity<Tag, U>) -> Self::Output {
+                Quantity(self.0.$fn(rhs.0))
+            }
+        }
+
+        impl<Tag: Unique, T: ops::$op_assign<U>, U> ops::$op_assign<Quantity<Tag, U>>
+            for Quantity<Tag, T>
+        {
+            fn $fn_assign(&mut self, rhs: Quantity<Tag, U>) {
+                self.0.$fn_assign(rhs.0)
+            }
+        }
+    )*};
+}
+
+same_unit_op! {
+    Add::add, AddAssign::add_assign;
+    Sub::sub, SubAssign::sub_assign;
+    Rem::rem, RemAssign::rem_assign;
+}
+
+impl<Tag: Unique, T: ops::Neg> ops::Neg for Quantity<Tag, T> {
+    type Output = Quantity<Tag, T::Output>;
+
+    fn neg(self) -> Self::Output {
+        Quantity(-self.0)
+    }
+}
+
+// The following traits are implemented manually
+// as deriving them would require `Tag` to implement them too
+
+impl<Tag: Unique, T: Clone> Clone for Quantity<Tag, T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<Tag: Unique, T: Copy> Copy for Quantity<Tag, T> {}
+
+impl<Tag: Unique, T: PartialEq> PartialEq for Quantity<Tag, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<Tag: Unique, T: Eq> Eq for Quantity<Tag, T> {}
+
+impl<Tag: Unique, T: PartialOrd> PartialOrd for Quantity<Tag, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        self.0.partial_cmp(&other.0)
+    }
+}
+
+impl<Tag: Unique, T: Ord> Ord for Quantity<Tag, T> {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+impl<Tag: Unique, T: hash::Hash> hash::Hash for Quantity<Tag, T> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+impl<Tag: Unique, T: fmt::Display> fmt::Display for Quantity<Tag, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<Tag: Unique, T: fmt::Debug> fmt::Debug for Quantity<Tag, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Quantity").field(&*self.0).finish()
+    }
+}
@@ -0,0 +1,74 @@
+use core::marker::PhantomData;
+
+use crate::{pvt, Brand};
+
+/// A unique type generated by a call to [`with_unique`]
+///
+/// The uniqueness of this type is based on the lifetime `'id` which is
+/// chosen by the compiler for every call to [`with_unique`] and that can't be
+/// unified with any other lifetime, thus making every call generate a different type.
+///
+/// Its [`Brand`] is only ever handed out once, as the argument of the
+/// closure passed to [`with_unique`].
+pub struct Scoped<'id>(PhantomData<fn(&'id ()) -> &'id ()>);
+
+impl pvt::Unique for Scoped<'_> {}
+
+/// Calls `f` with the [`Brand`] of a unique type that is valid only inside of it
+///
+/// This works on the stable toolchain as it doesn't rely on any unstable feature:
+/// the type is made unique by an invariant lifetime which, being bound by
+/// the closure itself, is different for every call and can't escape from it.
+///
+/// # Example
+///
+/// Two brands generated from different calls will always have different types,
+/// thus this won't compile:
+/// ```compile_fail E0521
+/// fn same<Tag: unique_type::Unique>(a: unique_type::Brand<Tag>, b: unique_type::Brand<Tag>) {}
+///
+/// unique_type::with_unique(|a| unique_type::with_unique(|b| same(a, b)));
+/// ```
+///
+/// And this won't compile either, because the type can't outlive the closure:
+/// ```compile_fail E0521
+/// let mut escaped = None;
+/// unique_type::with_unique(|brand| escaped = Some(brand));
+/// ```
+///
+/// # Scoping
+///
+/// Differently from the types generated from [`new!`](crate::new!), which can be
+/// stored in type aliases and statics, nothing tagged with this type can outlive
+/// the closure, thus only unbranded results can be returned from it:
+#[cfg_attr(feature = "alloc", doc = " ```")]
+#[cfg_attr(not(feature = "alloc"), doc = " ```ignore")]
+/// use unique_type::vec::BrandedVec;
+///
+/// let sum: u32 = unique_type::with_unique(|brand| {
+///     let mut vec = BrandedVec::new(brand);
+///     let a = vec.push(1);
+///     let b = vec.push(2);
+///     vec[a] + vec[b]
+/// });
+/// ```
+///
+/// While branded values, like containers and their indices, can't escape:
+#[cfg_attr(feature = "alloc", doc = " ```compile_fail E0521")]
+#[cfg_attr(not(feature = "alloc"), doc = " ```ignore")]
+/// use unique_type::vec::BrandedVec;
+///
+/// let mut vec = None;
+/// unique_type::with_unique(|brand| vec = Some(BrandedVec::<_, u32>::new(brand)));
+/// ```
+#[cfg_attr(feature = "alloc", doc = " ```compile_fail E0521")]
+#[cfg_attr(not(feature = "alloc"), doc = " ```ignore")]
+/// use unique_type::vec::BrandedVec;
+///
+/// let mut index = None;
+/// unique_type::with_unique(|brand| index = Some(BrandedVec::new(brand).push(0)));
+/// ```
+pub fn with_unique<R>(f: impl for<'id> FnOnce(Brand<Scoped<'id>>) -> R) -> R {
+    // SAFETY: the lifetime is chosen for this call only, so this is the only brand of the type
+    f(unsafe { Brand::new_unchecked() })
+}
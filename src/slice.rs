@@ -0,0 +1,210 @@
+//! A slice whose ranges are statically tied to it
+//!
+//! A [`BrandedSlice`] is tagged with a [`Unique`] type which is shared by all the
+//! [`BrandedRange`]s created from it, those can be split and narrowed while remaining
+//! in bounds, and can then only be used with the slice they were created from,
+//! without requiring any bound check.
+//!
+//! # Example
+//!
+//! ```
+//! use unique_type::slice::BrandedSlice;
+//!
+//! unique_type::with_unique(|brand| {
+//!     let input = BrandedSlice::new(brand, b"key=value".as_slice());
+//!     let range = input.range();
+//!     let eq = input.iter().position(|&b| b == b'=').unwrap();
+//!     let (key, value) = range.split_at(eq).unwrap();
+//!     let value = value.narrow(1..).unwrap();
+//!     assert_eq!(&input[key], b"key");
+//!     assert_eq!(&input[value], b"value");
+//! });
+//! ```
+//!
+//! Using a range with a slice it doesn't belong to results in a compiler error:
+#![cfg_attr(feature = "nightly", doc = " ```compile_fail E0308")]
+#![cfg_attr(not(feature = "nightly"), doc = " ```ignore")]
+//! # fn main() {
+//! use unique_type::slice::BrandedSlice;
+//!
+//! let a = BrandedSlice::new(unique_type::new_value!(), &[0, 1, 2]);
+//! let b = BrandedSlice::new(unique_type::new_value!(), &[0]);
+//! // a and b have two different tags
+//! b[a.range()];
+//! # }
+//! ```
+//!
+//! And the same goes for types generated from [`with_unique`](crate::with_unique):
+//! ```compile_fail E0521
+//! use unique_type::{slice::BrandedSlice, with_unique};
+//!
+//! with_unique(|a| with_unique(|b| {
+//!     let a = BrandedSlice::new(a, &[0, 1, 2]);
+//!     let b = BrandedSlice::new(b, &[0]);
+//!     b[a.range()];
+//! }));
+//! ```
+
+use core::{
+    fmt,
+    marker::PhantomData,
+    ops::{self, Bound, RangeBounds},
+    slice,
+};
+
+use crate::{Brand, Unique};
+
+/// A slice tagged with the unique type `Tag`
+///
+/// The tag guarantees that a [`BrandedRange`] can only be used with the slice that created it,
+/// and since the length of the slice can't change such a range is always in bounds.
+pub struct BrandedSlice<'a, Tag: Unique, T> {
+    slice: &'a [T],
+    _tag: PhantomData<Tag>,
+}
+
+impl<'a, Tag: Unique, T> BrandedSlice<'a, Tag, T> {
+    /// Tags `slice` with `Tag`
+    ///
+    /// The brand is consumed, this guarantees that no other slice can have the same tag.
+    pub fn new(_brand: Brand<Tag>, slice: &'a [T]) -> Self {
+        Self {
+            slice,
+            _tag: PhantomData,
+        }
+    }
+
+    /// Returns the range that spans the whole slice
+    pub fn range(&self) -> BrandedRange<Tag> {
+        BrandedRange::new(0, self.slice.len())
+    }
+
+    /// Returns the given range of the slice,
+    /// or [`None`] if it's out of bounds
+    pub fn range_of(&self, range: impl RangeBounds<usize>) -> Option<BrandedRange<Tag>> {
+        self.range().narrow(range)
+    }
+
+    /// Returns the elements of the slice in the given range
+    pub fn get(&self, range: BrandedRange<Tag>) -> &'a [T] {
+        // SAFETY: the range has been generated from this slice (it has the same tag)
+        // and only ever narrowed, so it's in bounds
+        unsafe { self.slice.get_unchecked(range.start..range.end) }
+    }
+
+    /// Returns the number of elements in the slice
+    pub fn len(&self) -> usize {
+        self.slice.len()
+    }
+
+    /// Returns `true` if the slice contains no elements
+    pub fn is_empty(&self) -> bool {
+        self.slice.is_empty()
+    }
+
+    /// Returns an iterator over the elements of the slice
+    pub fn iter(&self) -> slice::Iter<'a, T> {
+        self.slice.iter()
+    }
+
+    /// Returns the underlying slice
+    pub fn as_slice(&self) -> &'a [T] {
+        self.slice
+    }
+}
+
+impl<Tag: Unique, T> ops::Index<BrandedRange<Tag>> for BrandedSlice<'_, Tag, T> {
+    type Output = [T];
+
+    fn index(&self, range: BrandedRange<Tag>) -> &[T] {
+        self.get(range)
+    }
+}
+
+// The following traits are implemented manually
+// as deriving them would require `Tag` (and `T`) to implement them too
+
+impl<Tag: Unique, T> Clone for BrandedSlice<'_, Tag, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Tag: Unique, T> Copy for BrandedSlice<'_, Tag, T> {}
+
+impl<Tag: Unique, T: fmt::Debug> fmt::Debug for BrandedSlice<'_, Tag, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.slice.fmt(f)
+    }
+}
+
+/// A range of a [`BrandedSlice`] tagged with the unique type `Tag`
+///
+/// It can only be generated from the slice with the same tag, thus it's always in bounds.
+pub struct BrandedRange<Tag: Unique> {
+    start: usize,
+    end: usize,
+    _tag: PhantomData<Tag>,
+}
+
+impl<Tag: Unique> BrandedRange<Tag> {
+    const fn new(start: usize, end: usize) -> Self {
+        Self {
+            start,
+            end,
+            _tag: PhantomData,
+        }
+    }
+
+    /// Returns the position of the first element of the range
+    pub const fn start(self) -> usize {
+        self.start
+    }
+
+    /// Returns the position after the last element of the range
+    pub const fn end(self) -> usize {
+        self.end
+    }
+
+    /// Returns the number of elements in the range
+    pub const fn len(self) -> usize {
+        self.end - self.start
+    }
+
+    /// Returns `true` if the range contains no elements
+    pub const fn is_empty(self) -> bool {
+        self.start == self.end
+    }
+
+    /// Divides the range into two at `mid` (relative to the start of the range),
+    /// or returns [`None`] if `mid` is greater than the length of the range
+    pub const fn split_at(self, mid: usize) -> Option<(Self, Self)> {
+        if mid > self.len() {
+            return None;
+        }
+        let mid = self.start + mid;
+        Some((Self::new(self.start, mid), Self::new(mid, self.end)))
+    }
+
+    /// Returns the sub-range described by `range` (relative to the start of the range),
+    /// or [`None`] if it's not contained in this range
+    pub fn narrow(self, range: impl RangeBounds<usize>) -> Option<Self> {
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start.checked_add(1)?,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end.checked_add(1)?,
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => self.len(),
+        };
+        (start <= end && end <= self.len()).then(|| Self::new(self.start + start, self.start + end))
+    }
+}
+
+id_traits!(
+    BrandedRange<Tag>,
+    |range| (range.start, range.end),
+    Debug(|range, f| (range.start..range.end).fmt(f))
+);
@@ -0,0 +1,199 @@
+//! Typestates whose transitions invalidate the handles of the previous state
+//!
+//! A [`State`] is a value tagged with a [`Unique`] type, the values tagged
+//! through it act as handles that can only be used with the state that generated them.
+//! Every transition changes the state tag with a fresh one, thus handles obtained
+//! before a transition can't be used after it.
+//!
+//! The new tag can come from the brand passed to [`State::transition`], from the scope
+//! opened by [`State::transition_scoped`], or from the [`transition!`](crate::transition!)
+//! macro. Like [`new_value!`](crate::new_value!), each expansion of the macro can only be
+//! evaluated once per program, so transitions that can run more than once (in loops or
+//! in functions called many times) must use one of the two methods instead.
+//!
+//! # Example
+//!
+//! ```
+//! use unique_type::{state::State, with_unique, Tagged, Unique};
+//!
+//! struct Closed;
+//! struct Open(Vec<u8>);
+//!
+//! fn read<Tag: Unique>(file: &State<Tag, Open>, cursor: Tagged<Tag, usize>) -> u8 {
+//!     file.get().0[*cursor]
+//! }
+//!
+//! with_unique(|closed| with_unique(|open| {
+//!     let file = State::new(closed, Closed);
+//!     let file = file.transition(open, |Closed| Open(vec![1, 2, 3]));
+//!     let cursor = file.tag(1);
+//!     assert_eq!(read(&file, cursor), 2);
+//! }));
+//! ```
+//!
+//! Using a handle from a previous state results in a compiler error:
+#![cfg_attr(feature = "nightly", doc = " ```compile_fail E0308")]
+#![cfg_attr(not(feature = "nightly"), doc = " ```ignore")]
+//! # fn main() {
+//! use unique_type::{state::State, transition, Tagged, Unique};
+//!
+//! struct Open(Vec<u8>);
+//!
+//! fn read<Tag: Unique>(file: &State<Tag, Open>, cursor: Tagged<Tag, usize>) -> u8 {
+//!     file.get().0[*cursor]
+//! }
+//!
+//! let file = State::new(unique_type::new_value!(), Open(vec![1, 2, 3]));
+//! let cursor = file.tag(1);
+//! let file = transition!(file, |Open(data)| Open(data[1..].to_vec()));
+//! // cursor belongs to the previous state
+//! read(&file, cursor);
+//! # }
+//! ```
+//!
+//! And the same goes for types generated from [`with_unique`](crate::with_unique):
+//! ```compile_fail E0521
+//! use unique_type::{state::State, with_unique, Tagged, Unique};
+//!
+//! fn read<Tag: Unique>(file: &State<Tag, Vec<u8>>, cursor: Tagged<Tag, usize>) -> u8 {
+//!     file.get()[*cursor]
+//! }
+//!
+//! with_unique(|a| with_unique(|b| {
+//!     let file = State::new(a, vec![1, 2, 3]);
+//!     let cursor = file.tag(1);
+//!     let file = file.transition(b, |data| data[1..].to_vec());
+//!     read(&file, cursor);
+//! }));
+//! ```
+
+use core::fmt;
+
+use crate::{brand, Brand, Scoped, Tagged, Unique};
+
+/// A value in the state `S` tagged with the unique type `Tag`
+///
+/// The state owns the brand of its tag, thus no other state can have the same tag
+/// and the values tagged through [`State::tag`] can only be used with this state.
+pub struct State<Tag: Unique, S> {
+    brand: Brand<Tag>,
+    value: S,
+}
+
+impl<Tag: Unique, S> State<Tag, S> {
+    /// Constructs the initial state tagged with `Tag`
+    ///
+    /// The brand is consumed, this guarantees that no other state can have the same tag.
+    pub const fn new(brand: Brand<Tag>, value: S) -> Self {
+        Self { brand, value }
+    }
+
+    /// Moves to a new state tagged with the unique type of the given brand
+    ///
+    /// The brand of the current state is dropped, invalidating all of its handles.
+    /// The [`transition!`](crate::transition!) macro generates the new brand automatically.
+    pub fn transition<NewTag: Unique, T>(
+        self,
+        brand: Brand<NewTag>,
+        f: impl FnOnce(S) -> T,
+    ) -> State<NewTag, T> {
+        State::new(brand, f(self.value))
+    }
+
+    /// Moves to a new state tagged with a unique type generated by
+    /// [`with_unique`](crate::with_unique), and passes it to `scope`
+    ///
+    /// The brand of the current state is dropped, invalidating all of its handles.
+    /// Unlike [`transition!`](crate::transition!) it can be called any number of times.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use unique_type::{state::State, with_unique};
+    ///
+    /// fn increment(n: usize) -> usize {
+    ///     with_unique(|brand| {
+    ///         State::new(brand, n).transition_scoped(|n| n + 1, |state| state.into_inner())
+    ///     })
+    /// }
+    ///
+    /// assert_eq!(increment(increment(0)), 2);
+    /// ```
+    pub fn transition_scoped<T, R>(
+        self,
+        f: impl FnOnce(S) -> T,
+        scope: impl for<'id> FnOnce(State<Scoped<'id>, T>) -> R,
+    ) -> R {
+        let value = f(self.value);
+        crate::with_unique(|brand| scope(State::new(brand, value)))
+    }
+
+    /// Tags `value` with the tag of this state, making it an handle
+    /// that can't be used after a transition
+    pub const fn tag<T>(&self, value: T) -> Tagged<Tag, T> {
+        Tagged::new(&self.brand, value)
+    }
+
+    /// Returns the brand of the state tag
+    pub const fn brand(&self) -> &Brand<Tag> {
+        &self.brand
+    }
+
+    /// Returns a reference to the value of the state
+    pub const fn get(&self) -> &S {
+        &self.value
+    }
+
+    /// Returns a mutable reference to the value of the state
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.value
+    }
+
+    /// Consumes the state, returning its value
+    pub fn into_inner(self) -> S {
+        self.value
+    }
+}
+
+/// Shows the label of the unique type and where it was generated, if known
+impl<Tag: Unique, S: fmt::Debug> fmt::Debug for State<Tag, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut tuple = f.debug_tuple("State");
+        tuple.field(&self.value);
+        brand::debug_tag::<Tag>(&mut tuple);
+        tuple.finish()
+    }
+}
+
+/// Moves a [`State`](crate::state::State) to a new state tagged with a freshly generated unique type
+///
+/// It's a shorthand for calling [`State::transition`](crate::state::State::transition)
+/// with the brand returned by [`new_value!`](crate::new_value!), the generated type can be
+/// labelled by passing the label after the mapping function.
+///
+/// # Panics
+///
+/// Just like [`new_value!`](crate::new_value!), it panics if the same expansion
+/// of the macro is evaluated more than once, use
+/// [`State::transition_scoped`](crate::state::State::transition_scoped) for repeatable transitions.
+///
+/// # Example
+///
+#[cfg_attr(feature = "nightly", doc = " ```")]
+#[cfg_attr(not(feature = "nightly"), doc = " ```ignore")]
+/// # fn main() {
+/// use unique_type::{state::State, transition};
+///
+/// let counter = State::new(unique_type::new_value!(), 0);
+/// let counter = transition!(counter, |n| n + 1);
+/// let counter = transition!(counter, |n| n + 1, Incremented);
+/// assert_eq!(counter.into_inner(), 2);
+/// # }
+/// ```
+#[cfg(feature = "nightly")]
+#[macro_export]
+macro_rules! transition {
+    ($state:expr, $f:expr $(, $label:tt)?) => {
+        $crate::state::State::transition($state, $crate::new_value!($($label)?), $f)
+    };
+}
@@ -0,0 +1,240 @@
+use core::{cmp, fmt, hash, marker::PhantomData, ops};
+
+use crate::{brand, Brand, Unique};
+
+/// A value of type `T` tagged with the unique type `Tag`
+///
+/// All the common traits are forwarded to the wrapped value, and the operators are
+/// implemented only between values with the same tag, thus values that belong to
+/// different owners can't be mixed together.
+///
+/// # Example
+///
+/// ```
+/// use unique_type::Tagged;
+///
+/// unique_type::with_unique(|brand| {
+///     let a = Tagged::new(&brand, 1u64);
+///     let b = Tagged::new(&brand, 2u64);
+///     assert_eq!((a + b).into_inner(), 3);
+///     assert_eq!(a.map(|a| a * 10), Tagged::new(&brand, 10));
+/// });
+/// ```
+///
+/// Mixing values with different tags results in a compiler error:
+#[cfg_attr(feature = "nightly", doc = " ```compile_fail E0308")]
+#[cfg_attr(not(feature = "nightly"), doc = " ```ignore")]
+/// # fn main() {
+/// use unique_type::Tagged;
+///
+/// let a = Tagged::new(&unique_type::new_value!(), 1u64);
+/// let b = Tagged::new(&unique_type::new_value!(), 2u64);
+/// // a and b have two different tags
+/// a + b;
+/// # }
+/// ```
+///
+/// And the same goes for types generated from [`with_unique`](crate::with_unique):
+/// ```compile_fail E0521
+/// use unique_type::{with_unique, Tagged};
+///
+/// with_unique(|a| with_unique(|b| {
+///     Tagged::new(&a, 1u64) + Tagged::new(&b, 2u64);
+/// }));
+/// ```
+#[repr(transparent)]
+pub struct Tagged<Tag: Unique, T> {
+    value: T,
+    _tag: PhantomData<Tag>,
+}
+
+impl<Tag: Unique, T> Tagged<Tag, T> {
+    /// Tags `value` with the unique type of the given brand
+    pub const fn new(_brand: &Brand<Tag>, value: T) -> Self {
+        Self::from_inner(value)
+    }
+
+    /// Tags `value` without requiring a brand, this is meant for the
+    /// unique types that don't have one (like [`Product`](crate::quantity::Product))
+    /// or whose brand has been split into capabilities
+    pub(crate) const fn from_inner(value: T) -> Self {
+        Self {
+            value,
+            _tag: PhantomData,
+        }
+    }
+
+    /// Consumes the tagged value, returning the wrapped one
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    /// Maps the wrapped value to another one with the same tag
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Tagged<Tag, U> {
+        Tagged {
+            value: f(self.value),
+            _tag: PhantomData,
+        }
+    }
+
+    /// Converts from `&Tagged<Tag, T>` to `Tagged<Tag, &T>`
+    pub const fn as_ref(&self) -> Tagged<Tag, &T> {
+        Tagged {
+            value: &self.value,
+            _tag: PhantomData,
+        }
+    }
+}
+
+impl<Tag: Unique, T> ops::Deref for Tagged<Tag, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+// The following traits are implemented manually
+// as deriving them would require `Tag` to implement them too
+
+impl<Tag: Unique, T: Clone> Clone for Tagged<Tag, T> {
+    fn clone(&self) -> Self {
+        self.as_ref().map(T::clone)
+    }
+}
+
+impl<Tag: Unique, T: Copy> Copy for Tagged<Tag, T> {}
+
+impl<Tag: Unique, T: PartialEq> PartialEq for Tagged<Tag, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<Tag: Unique, T: Eq> Eq for Tagged<Tag, T> {}
+
+impl<Tag: Unique, T: PartialOrd> PartialOrd for Tagged<Tag, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        self.value.partial_cmp(&other.value)
+    }
+}
+
+impl<Tag: Unique, T: Ord> Ord for Tagged<Tag, T> {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.value.cmp(&other.value)
+    }
+}
+
+impl<Tag: Unique, T: hash::Hash> hash::Hash for Tagged<Tag, T> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.value.hash(state)
+    }
+}
+
+impl<Tag: Unique, T: fmt::Display> fmt::Display for Tagged<Tag, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+/// Shows the label of the unique type and where it was generated, if known
+impl<Tag: Unique, T: fmt::Debug> fmt::Debug for Tagged<Tag, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut tuple = f.debug_tuple("Tagged");
+        tuple.field(&self.value);
+        brand::debug_tag::<Tag>(&mut tuple);
+        tuple.finish()
+    }
+}
+
+/// Implements a binary operator (and its assigning version)
+/// between two values with the same tag
+macro_rules! binary_op {
+    ($($op:ident::$fn:ident, $op_assign:ident::$fn_assign:ident;)*) => {$(
+        impl<Tag: Unique, T: ops::$op<U>, U> ops::$op<Tagged<Tag, U>> for Tagged<Tag, T> {
+            type Output = Tagged<Tag, T::Output>;
+
+            fn $fn(self, rhs: Tagged<Tag, U>) -> Self::Output {
+                self.map(|value| value.$fn(rhs.value))
+            }
+        }
+
+        impl<Tag: Unique, T: ops::$op_assign<U>, U> ops::$op_assign<Tagged<Tag, U>>
+            for Tagged<Tag, T>
+        {
+            fn $fn_assign(&mut self, rhs: Tagged<Tag, U>) {
+                self.value.$fn_assign(rhs.value)
+            }
+        }
+    )*};
+}
+
+binary_op! {
+    Add::add, AddAssign::add_assign;
+    Sub::sub, SubAssign::sub_assign;
+    Mul::mul, MulAssign::mul_assign;
+    Div::div, DivAssign::div_assign;
+    Rem::rem, RemAssign::rem_assign;
+    BitAnd::bitand, BitAndAssign::bitand_assign;
+    BitOr::bitor, BitOrAssign::bitor_assign;
+    BitXor::bitxor, BitXorAssign::bitxor_assign;
+    Shl::shl, ShlAssign::shl_assign;
+    Shr::shr, ShrAssign::shr_assign;
+}
+
+/// Implements an unary operator
+macro_rules! unary_op {
+    ($($op:ident::$fn:ident;)*) => {$(
+        impl<Tag: Unique, T: ops::$op> ops::$op for Tagged<Tag, T> {
+            type Output = Tagged<Tag, T::Output>;
+
+            fn $fn(self) -> Self::Output {
+                self.map(T::$fn)
+            }
+        }
+    )*};
+}
+
+unary_op! {
+    Neg::neg;
+    Not::not;
+}
+
+#[cfg(feature = "serde")]
+impl<Tag: Unique, T: serde::Serialize> serde::Serialize for Tagged<Tag, T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.value.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<Tag: Unique, T> Tagged<Tag, T> {
+    /// Deserializes a value and tags it with the unique type of the given brand
+    ///
+    /// [`Deserialize`](serde::Deserialize) is not implemented as it would allow
+    /// to tag any value without the brand.
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "serde", doc = " ```")]
+    #[cfg_attr(not(feature = "serde"), doc = " ```ignore")]
+    /// use unique_type::Tagged;
+    ///
+    /// unique_type::with_unique(|brand| {
+    ///     let value = Tagged::new(&brand, 42u64);
+    ///     let json = serde_json::to_string(&value).unwrap();
+    ///     assert_eq!(json, "42");
+    ///
+    ///     let mut deserializer = serde_json::Deserializer::from_str(&json);
+    ///     let rebuilt = Tagged::deserialize(&brand, &mut deserializer).unwrap();
+    ///     assert_eq!(rebuilt, value);
+    /// });
+    /// ```
+    pub fn deserialize<'de, D>(brand: &Brand<Tag>, deserializer: D) -> Result<Self, D::Error>
+    where
+        T: serde::Deserialize<'de>,
+        D: serde::Deserializer<'de>,
+    {
+        T::deserialize(deserializer).map(|value| Self::new(brand, value))
+    }
+}
@@ -0,0 +1,177 @@
+//! A vector whose indices are statically tied to it
+//!
+//! A [`BrandedVec`] is tagged with a [`Unique`] type which is shared by all the
+//! [`Index`]es it generates, those can then only be used with the vector
+//! that generated them, without requiring any bound check.
+//!
+//! # Example
+//!
+//! ```
+//! use unique_type::vec::BrandedVec;
+//!
+//! unique_type::with_unique(|brand| {
+//!     let mut vec = BrandedVec::new(brand);
+//!     let a = vec.push("a");
+//!     let b = vec.push("b");
+//!     assert_eq!(vec[a], "a");
+//!     assert_eq!(vec[b], "b");
+//! });
+//! ```
+//!
+//! Using an index with a vector it doesn't belong to results in a compiler error:
+#![cfg_attr(feature = "nightly", doc = " ```compile_fail E0308")]
+#![cfg_attr(not(feature = "nightly"), doc = " ```ignore")]
+//! # fn main() {
+//! use unique_type::vec::BrandedVec;
+//!
+//! let mut a = BrandedVec::new(unique_type::new_value!());
+//! let b = BrandedVec::<_, usize>::new(unique_type::new_value!());
+//! let index = a.push(0);
+//! // a and b have two different tags
+//! b[index];
+//! # }
+//! ```
+//!
+//! And the same goes for types generated from [`with_unique`](crate::with_unique):
+//! ```compile_fail E0521
+//! use unique_type::{vec::BrandedVec, with_unique};
+//!
+//! with_unique(|a| with_unique(|b| {
+//!     let mut a = BrandedVec::new(a);
+//!     let b = BrandedVec::<_, usize>::new(b);
+//!     let index = a.push(0);
+//!     b[index];
+//! }));
+//! ```
+
+use alloc::vec::Vec;
+use core::{fmt, marker::PhantomData, ops, slice};
+
+use crate::{Brand, Unique};
+
+/// An append-only vector tagged with the unique type `Tag`
+///
+/// The tag guarantees that an [`Index`] can only be used with the vector that created it,
+/// and since elements can never be removed such an index is always in bounds.
+pub struct BrandedVec<Tag: Unique, T> {
+    vec: Vec<T>,
+    _tag: PhantomData<Tag>,
+}
+
+impl<Tag: Unique, T> BrandedVec<Tag, T> {
+    /// Constructs a new empty vector tagged with `Tag`
+    ///
+    /// The brand is consumed, this guarantees that no other vector can have the same tag.
+    pub fn new(_brand: Brand<Tag>) -> Self {
+        Self {
+            vec: Vec::new(),
+            _tag: PhantomData,
+        }
+    }
+
+    /// Appends an element to the back of the vector, returning its index
+    pub fn push(&mut self, value: T) -> Index<Tag> {
+        let index = Index::new(self.vec.len());
+        self.vec.push(value);
+        index
+    }
+
+    /// Returns the index of the element at position `index`,
+    /// or [`None`] if it's out of bounds
+    pub fn checked_index(&self, index: usize) -> Option<Index<Tag>> {
+        (index < self.vec.len()).then(|| Index::new(index))
+    }
+
+    /// Returns an iterator over the indices of all the elements of the vector
+    pub fn indices(&self) -> impl Iterator<Item = Index<Tag>> {
+        (0..self.vec.len()).map(Index::new)
+    }
+
+    /// Returns a reference to the element at the given index
+    pub fn get(&self, index: Index<Tag>) -> &T {
+        // SAFETY: the index has been generated by this vector (it has the same tag)
+        // and the vector never shrinks
+        unsafe { self.vec.get_unchecked(index.index) }
+    }
+
+    /// Returns a mutable reference to the element at the given index
+    pub fn get_mut(&mut self, index: Index<Tag>) -> &mut T {
+        // SAFETY: the index has been generated by this vector (it has the same tag)
+        // and the vector never shrinks
+        unsafe { self.vec.get_unchecked_mut(index.index) }
+    }
+
+    /// Returns the number of elements in the vector
+    pub fn len(&self) -> usize {
+        self.vec.len()
+    }
+
+    /// Returns `true` if the vector contains no elements
+    pub fn is_empty(&self) -> bool {
+        self.vec.is_empty()
+    }
+
+    /// Returns an iterator over the elements of the vector
+    pub fn iter(&self) -> slice::Iter<'_, T> {
+        self.vec.iter()
+    }
+
+    /// Returns an iterator that allows modifying each element of the vector
+    pub fn iter_mut(&mut self) -> slice::IterMut<'_, T> {
+        self.vec.iter_mut()
+    }
+
+    /// Extracts a slice containing all the elements of the vector
+    pub fn as_slice(&self) -> &[T] {
+        &self.vec
+    }
+
+    /// Consumes the vector, returning the underlying [`Vec`]
+    pub fn into_vec(self) -> Vec<T> {
+        self.vec
+    }
+}
+
+impl<Tag: Unique, T> ops::Index<Index<Tag>> for BrandedVec<Tag, T> {
+    type Output = T;
+
+    fn index(&self, index: Index<Tag>) -> &T {
+        self.get(index)
+    }
+}
+
+impl<Tag: Unique, T> ops::IndexMut<Index<Tag>> for BrandedVec<Tag, T> {
+    fn index_mut(&mut self, index: Index<Tag>) -> &mut T {
+        self.get_mut(index)
+    }
+}
+
+impl<Tag: Unique, T: fmt::Debug> fmt::Debug for BrandedVec<Tag, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.vec.fmt(f)
+    }
+}
+
+/// An index into a [`BrandedVec`] tagged with the unique type `Tag`
+///
+/// It can only be generated by the vector with the same tag, thus it's always in bounds.
+pub struct Index<Tag: Unique> {
+    index: usize,
+    _tag: PhantomData<Tag>,
+}
+
+impl<Tag: Unique> Index<Tag> {
+    const fn new(index: usize) -> Self {
+        Self {
+            index,
+            _tag: PhantomData,
+        }
+    }
+
+    /// Returns the position of the element this index refers to
+    pub const fn get(self) -> usize {
+        self.index
+    }
+}
+
+id_traits!(Index<Tag>, |index| index.index);
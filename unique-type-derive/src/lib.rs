@@ -0,0 +1,395 @@
+//! Procedural macros for the [`unique-type`](https://docs.rs/unique-type/latest/) crate
+//!
+//! This crate shouldn't be used directly, enable the `derive` feature
+//! of `unique-type` and use the macros re-exported from there instead.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{
+    parse_macro_input, parse_quote, punctuated::Punctuated, spanned::Spanned, Attribute, Data,
+    DeriveInput, Error, Fields, GenericParam, Ident, Index, Member, Path, Token,
+};
+
+/// The name of the field holding the tag, chosen to not collide with the user's ones
+const MARKER: &str = "__unique_type_tag";
+
+/// The traits that are derived by [`branded`] itself, as the
+/// built-in derives would require the tag to implement them too
+const DERIVES: [&str; 8] = [
+    "Clone",
+    "Copy",
+    "PartialEq",
+    "Eq",
+    "PartialOrd",
+    "Ord",
+    "Hash",
+    "Debug",
+];
+
+/// Makes a struct uniquely identifiable by tagging it with a unique type
+///
+/// The struct gets a new generic parameter bound by `unique_type::Unique`
+/// (named `Tag` by default, use `#[branded(Name)]` to choose another name)
+/// and a new [`PhantomData`](core::marker::PhantomData) field that holds it.
+///
+/// The following methods are also generated:
+/// - `new` which takes a reference to the `Brand` of the tag followed by the
+///   value of each field, in order
+/// - `rebrand` which converts the struct to one tagged with another unique type,
+///   given a reference to its `Brand`
+///
+/// The built-in derives of `Clone`, `Copy`, `PartialEq`, `Eq`, `PartialOrd`, `Ord`, `Hash`
+/// and `Debug` that follow the attribute are implemented by the macro itself, as the built-in
+/// ones would require the tag to implement them too. The other derives are left untouched,
+/// and the attribute must come before `#[derive]` for this to work.
+///
+/// # Example
+///
+/// ```
+/// #[unique_type_derive::branded]
+/// struct Struct {
+///     value: usize,
+/// }
+///
+/// fn foo<Tag: unique_type::Unique>(a: Struct<Tag>, b: Struct<Tag>) -> usize {
+///     a.value + b.value
+/// }
+///
+/// unique_type::with_unique(|brand| {
+///     let a = Struct::new(&brand, 1);
+///     let b = Struct::new(&brand, 2);
+///     assert_eq!(foo(a, b), 3);
+/// });
+/// ```
+///
+/// Values with different tags can't be mixed together:
+/// ```compile_fail E0521
+/// # #[unique_type_derive::branded]
+/// # struct Struct(usize);
+/// fn foo<Tag: unique_type::Unique>(a: Struct<Tag>, b: Struct<Tag>) {}
+///
+/// unique_type::with_unique(|a| unique_type::with_unique(|b| {
+///     foo(Struct::new(&a, 1), Struct::new(&b, 2))
+/// }));
+/// ```
+///
+/// Unless one of them is rebranded:
+/// ```
+/// # #[unique_type_derive::branded]
+/// # struct Struct(usize);
+/// fn foo<Tag: unique_type::Unique>(a: Struct<Tag>, b: Struct<Tag>) {}
+///
+/// unique_type::with_unique(|a| unique_type::with_unique(|b| {
+///     foo(Struct::new(&a, 1).rebrand(&b), Struct::new(&b, 2))
+/// }));
+/// ```
+///
+/// The common traits can be derived as usual:
+/// ```
+/// #[unique_type_derive::branded]
+/// #[derive(Clone, Copy, PartialEq, Debug)]
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// unique_type::with_unique(|brand| {
+///     let a = Point::new(&brand, 1, 2);
+///     assert_eq!(a.clone(), a);
+///     assert_eq!(format!("{a:?}"), "Point { x: 1, y: 2 }");
+/// });
+/// ```
+///
+/// The names of the generated field and parameters don't collide with the ones of the struct:
+/// ```
+/// #[unique_type_derive::branded]
+/// #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+/// struct Names<T> {
+///     _tag: T,
+///     _brand: T,
+/// }
+///
+/// #[unique_type_derive::branded]
+/// #[derive(Clone, Copy, Debug)]
+/// struct Unit;
+///
+/// unique_type::with_unique(|brand| {
+///     let names = Names::new(&brand, 1, 2);
+///     assert!(names.clone() < Names::new(&brand, 1, 3));
+///     assert_eq!(format!("{:?}", Unit::new(&brand)), "Unit");
+/// });
+/// ```
+///
+/// The tag is added after the lifetimes of the struct, and it can be renamed:
+/// ```
+/// #[unique_type_derive::branded(Id)]
+/// struct Ref<'a, T>(&'a T);
+///
+/// fn get<'a, Id: unique_type::Unique, T>(r: Ref<'a, Id, T>) -> &'a T {
+///     r.0
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn branded(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let tag = if attr.is_empty() {
+        Ident::new("Tag", Span::call_site())
+    } else {
+        parse_macro_input!(attr as Ident)
+    };
+    let input = parse_macro_input!(item as DeriveInput);
+    expand(tag, input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand(tag: Ident, mut input: DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Struct(data) = &mut input.data else {
+        return Err(Error::new(input.span(), "only structs can be branded"));
+    };
+
+    let derives = take_derives(&mut input.attrs)?;
+
+    // The tag is added after the lifetimes, as they have to come first
+    let position = input.generics.lifetimes().count();
+    input
+        .generics
+        .params
+        .insert(position, parse_quote!(#tag: ::unique_type::Unique));
+
+    // Every field is collected before adding the marker, as it doesn't have to be initialized
+    let members: Vec<Member> = data.fields.members().collect();
+    let types: Vec<_> = data.fields.iter().map(|field| field.ty.clone()).collect();
+    let args: Vec<Ident> = members
+        .iter()
+        .map(|member| match member {
+            Member::Named(ident) => ident.clone(),
+            Member::Unnamed(Index { index, .. }) => format_ident!("_{index}"),
+        })
+        .collect();
+
+    let shape = Shape::of(&data.fields);
+    let marker_type = quote!(::core::marker::PhantomData<#tag>);
+    let marker = match &mut data.fields {
+        Fields::Named(fields) => {
+            let name = Ident::new(MARKER, Span::call_site());
+            fields.named.push(parse_quote!(#name: #marker_type));
+            Member::Named(name)
+        }
+        Fields::Unnamed(fields) => {
+            fields.unnamed.push(parse_quote!(#marker_type));
+            Member::Unnamed(Index::from(fields.unnamed.len() - 1))
+        }
+        Fields::Unit => {
+            data.fields = Fields::Unnamed(parse_quote!((#marker_type)));
+            Member::Unnamed(Index::from(0))
+        }
+    };
+    let ident = &input.ident;
+    let vis = &input.vis;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    // The generics of the rebranded type are the same, except for the tag
+    let new_tag = format_ident!("__New{tag}");
+    let rebranded_generics = input.generics.params.iter().map(|param| match param {
+        GenericParam::Type(param) if param.ident == tag => quote!(#new_tag),
+        GenericParam::Type(param) => {
+            let ident = &param.ident;
+            quote!(#ident)
+        }
+        GenericParam::Lifetime(param) => {
+            let lifetime = &param.lifetime;
+            quote!(#lifetime)
+        }
+        GenericParam::Const(param) => {
+            let ident = &param.ident;
+            quote!(#ident)
+        }
+    });
+
+    let derived = derives
+        .iter()
+        .map(|derive| derive_trait(derive, &input, &tag, &members, &marker, shape));
+
+    Ok(quote! {
+        #input
+
+        #(#derived)*
+
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// Constructs a new value tagged with the unique type of the given brand
+            #vis fn new(
+                __unique_type_brand: &::unique_type::Brand<#tag>,
+                #(#args: #types),*
+            ) -> Self {
+                Self {
+                    #(#members: #args,)*
+                    #marker: ::core::marker::PhantomData,
+                }
+            }
+
+            /// Converts this value to one tagged with the unique type of the given brand
+            #vis fn rebrand<#new_tag: ::unique_type::Unique>(
+                self,
+                __unique_type_brand: &::unique_type::Brand<#new_tag>,
+            ) -> #ident<#(#rebranded_generics),*> {
+                #ident {
+                    #(#members: self.#members,)*
+                    #marker: ::core::marker::PhantomData,
+                }
+            }
+        }
+    })
+}
+
+/// The shape of the struct before adding the marker field
+#[derive(Clone, Copy)]
+enum Shape {
+    Named,
+    Unnamed,
+    Unit,
+}
+
+impl Shape {
+    fn of(fields: &Fields) -> Self {
+        match fields {
+            Fields::Named(_) => Self::Named,
+            Fields::Unnamed(_) => Self::Unnamed,
+            Fields::Unit => Self::Unit,
+        }
+    }
+}
+
+/// Removes the traits in [`DERIVES`] from the `#[derive]` attributes, returning them
+fn take_derives(attrs: &mut Vec<Attribute>) -> syn::Result<Vec<Ident>> {
+    let mut taken = Vec::new();
+    let mut kept = Vec::new();
+    for attr in attrs.drain(..) {
+        if !attr.path().is_ident("derive") {
+            kept.push(attr);
+            continue;
+        }
+        let paths = attr.parse_args_with(Punctuated::<Path, Token![,]>::parse_terminated)?;
+        let mut others = Punctuated::<Path, Token![,]>::new();
+        for path in paths {
+            match path.get_ident() {
+                Some(ident) if DERIVES.iter().any(|derive| ident == derive) => {
+                    taken.push(ident.clone())
+                }
+                _ => others.push(path),
+            }
+        }
+        if !others.is_empty() {
+            kept.push(parse_quote!(#[derive(#others)]));
+        }
+    }
+    *attrs = kept;
+    Ok(taken)
+}
+
+/// Implements one of the traits in [`DERIVES`] without requiring the tag to implement it
+fn derive_trait(
+    derive: &Ident,
+    input: &DeriveInput,
+    tag: &Ident,
+    members: &[Member],
+    marker: &Member,
+    shape: Shape,
+) -> TokenStream2 {
+    let ident = &input.ident;
+    let path = match derive.to_string().as_str() {
+        "Clone" => quote!(::core::clone::Clone),
+        "Copy" => quote!(::core::marker::Copy),
+        "PartialEq" | "Eq" | "PartialOrd" | "Ord" => quote!(::core::cmp::#derive),
+        "Hash" => quote!(::core::hash::Hash),
+        _ => quote!(::core::fmt::Debug),
+    };
+
+    // Just like the built-in derives, every type parameter except the tag is bound by the trait
+    let mut generics = input.generics.clone();
+    let bounded: Vec<Ident> = generics
+        .type_params()
+        .filter(|param| param.ident != *tag)
+        .map(|param| param.ident.clone())
+        .collect();
+    let where_clause = generics.make_where_clause();
+    for param in &bounded {
+        where_clause.predicates.push(parse_quote!(#param: #path));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let body = match derive.to_string().as_str() {
+        "Clone" => quote! {
+            fn clone(&self) -> Self {
+                Self {
+                    #(#members: ::core::clone::Clone::clone(&self.#members),)*
+                    #marker: ::core::marker::PhantomData,
+                }
+            }
+        },
+        "PartialEq" => quote! {
+            fn eq(&self, other: &Self) -> bool {
+                true #(&& self.#members == other.#members)*
+            }
+        },
+        "PartialOrd" => quote! {
+            fn partial_cmp(&self, other: &Self) -> ::core::option::Option<::core::cmp::Ordering> {
+                #(
+                    match ::core::cmp::PartialOrd::partial_cmp(&self.#members, &other.#members) {
+                        ::core::option::Option::Some(::core::cmp::Ordering::Equal) => {}
+                        ordering => return ordering,
+                    }
+                )*
+                ::core::option::Option::Some(::core::cmp::Ordering::Equal)
+            }
+        },
+        "Ord" => quote! {
+            fn cmp(&self, other: &Self) -> ::core::cmp::Ordering {
+                #(
+                    match ::core::cmp::Ord::cmp(&self.#members, &other.#members) {
+                        ::core::cmp::Ordering::Equal => {}
+                        ordering => return ordering,
+                    }
+                )*
+                ::core::cmp::Ordering::Equal
+            }
+        },
+        "Hash" => quote! {
+            fn hash<__H: ::core::hash::Hasher>(&self, state: &mut __H) {
+                #(::core::hash::Hash::hash(&self.#members, state);)*
+            }
+        },
+        "Debug" => {
+            let name = ident.to_string();
+            let fields = members.iter().map(|member| match member {
+                Member::Named(field) => {
+                    let field_name = field.to_string();
+                    quote!(.field(#field_name, &self.#member))
+                }
+                Member::Unnamed(_) => quote!(.field(&self.#member)),
+            });
+            let builder = match shape {
+                Shape::Named => quote!(f.debug_struct(#name)),
+                Shape::Unnamed => quote!(f.debug_tuple(#name)),
+                Shape::Unit => quote!(return f.write_str(#name)),
+            };
+            let fields = match shape {
+                Shape::Unit => quote!(),
+                _ => quote!(#(#fields)*.finish()),
+            };
+            quote! {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    #builder #fields
+                }
+            }
+        }
+        // Copy and Eq have no items
+        _ => quote!(),
+    };
+
+    quote! {
+        impl #impl_generics #path for #ident #ty_generics #where_clause {
+            #body
+        }
+    }
+}